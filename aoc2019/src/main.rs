@@ -0,0 +1,121 @@
+// Runs the solutions for one or more days and prints their answers as a table:
+//
+//     aoc2019 <day>... [input...]
+//     aoc2019 all [input...]
+//
+// A single day reads its input from stdin when no input file is given. Several
+// days take one input file each, in the order the days are given, or read
+// day_N/input.txt from the current directory when no input file is given.
+
+use common::input::read_lines;
+use common::report::DayAnswers;
+
+use std::env;
+use std::process;
+
+const DAYS: [u32; 4] = [1, 2, 3, 4];
+
+fn usage() -> String {
+    let days: Vec<String> = DAYS.iter().map(|day| day.to_string()).collect();
+    format!(
+        "usage: aoc2019 <day>... [input...]\n       aoc2019 all [input...]\navailable days: {}",
+        days.join(", ")
+    )
+}
+
+fn day_1(lines: &[String]) -> Result<DayAnswers, String> {
+    let masses = day_1::parse(lines).map_err(|e| format!("invalid mass: {}", e))?;
+    Ok(day_1::day_answers(&masses))
+}
+
+fn day_2(lines: &[String]) -> Result<DayAnswers, String> {
+    let memory = day_2::parse(&lines.concat()).map_err(|e| format!("invalid program: {}", e))?;
+    day_2::day_answers(&memory).map_err(|e| e.to_string())
+}
+
+fn day_3(lines: &[String]) -> Result<DayAnswers, String> {
+    let wires = day_3::parse_wires(lines).map_err(|e| e.to_string())?;
+    let points: Vec<Vec<day_3::Vec2d>> = wires.iter().map(|wire| day_3::get_points(wire)).collect();
+    day_3::day_answers(&points).map_err(|e| e.to_string())
+}
+
+fn day_4(lines: &[String]) -> Result<DayAnswers, String> {
+    let range = day_4::parse_input(lines).map_err(|e| e.to_string())?;
+    Ok(day_4::day_answers(range))
+}
+
+fn run(day: u32, lines: &[String]) -> Result<DayAnswers, String> {
+    match day {
+        1 => day_1(lines),
+        2 => day_2(lines),
+        3 => day_3(lines),
+        4 => day_4(lines),
+        _ => Err(format!("unknown day: {}\n{}", day, usage())),
+    }
+}
+
+// Splits the arguments into the days to run, in order, and the input files
+// following them.
+fn parse_args(args: &[String]) -> Result<(Vec<u32>, &[String]), String> {
+    if args.first().map(String::as_str) == Some("all") {
+        return Ok((DAYS.to_vec(), &args[1..]));
+    }
+    let mut days = Vec::new();
+    for arg in args {
+        match arg.parse::<u32>() {
+            Ok(day) if DAYS.contains(&day) => days.push(day),
+            Ok(day) => return Err(format!("unknown day: {}\n{}", day, usage())),
+            Err(_) if days.is_empty() => return Err(format!("invalid day: {}\n{}", arg, usage())),
+            Err(_) => break,
+        }
+    }
+    if days.is_empty() {
+        return Err(usage());
+    }
+    let count = days.len();
+    Ok((days, &args[count..]))
+}
+
+// Picks the input file of every day, None standing for stdin.
+fn input_paths(days: &[u32], paths: &[String]) -> Result<Vec<Option<String>>, String> {
+    match (days.len(), paths.len()) {
+        (1, 0) => Ok(vec![None]),
+        (_, 0) => Ok(days
+            .iter()
+            .map(|day| Some(format!("day_{}/input.txt", day)))
+            .collect()),
+        (days, found) if days == found => Ok(paths.iter().cloned().map(Some).collect()),
+        (days, found) => Err(format!(
+            "expected {} input files, found {}\n{}",
+            days,
+            found,
+            usage()
+        )),
+    }
+}
+
+// Runs every day given in the arguments, stopping at the first one failing.
+fn run_all(args: &[String]) -> Result<Vec<DayAnswers>, String> {
+    let (days, paths) = parse_args(args)?;
+    let paths = input_paths(&days, paths)?;
+
+    let mut answers = Vec::with_capacity(days.len());
+    for (day, path) in days.iter().zip(paths.iter()) {
+        let path = path.as_deref();
+        let lines = read_lines(path)
+            .map_err(|e| format!("unable to read {}: {}", path.unwrap_or("stdin"), e))?;
+        answers.push(run(*day, &lines).map_err(|e| format!("Day {}: {}", day, e))?);
+    }
+    Ok(answers)
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match run_all(&args) {
+        Ok(answers) => print!("{}", DayAnswers::table(&answers)),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
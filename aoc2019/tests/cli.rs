@@ -0,0 +1,198 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// An input file in the temporary directory, named after the process so that
+// concurrent test runs do not collide, and removed again when dropped, even
+// when an assertion fails.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(contents: &str) -> TempFile {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "aoc2019_cli_{}_{}.txt",
+            process::id(),
+            NEXT.fetch_add(1, Ordering::SeqCst)
+        );
+        let path = env::temp_dir().join(name);
+        fs::write(&path, contents).unwrap();
+        TempFile(path)
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+// Runs aoc2019 with the arguments followed by one file for every input.
+fn run(args: &[&str], inputs: &[&str]) -> Output {
+    let inputs: Vec<TempFile> = inputs.iter().map(|input| TempFile::new(input)).collect();
+    Command::new(env!("CARGO_BIN_EXE_aoc2019"))
+        .args(args)
+        .args(inputs.iter().map(TempFile::path))
+        .output()
+        .unwrap()
+}
+
+// Splits the printed table into its rows of cells.
+fn table_rows(stdout: &[u8]) -> Vec<Vec<String>> {
+    String::from_utf8(stdout.to_vec())
+        .unwrap()
+        .lines()
+        .map(|line| {
+            line.split("  ")
+                .map(str::trim)
+                .filter(|cell| !cell.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn run_day_4_test() {
+    let output = run(
+        &["4"],
+        &["138241-674034
+"],
+    );
+
+    assert!(output.status.success());
+    let rows = table_rows(&output.stdout);
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0], ["Day", "Part 1", "Part 2", "Time"]);
+    assert_eq!(rows[1][..3], ["4", "1890", "1277"]);
+}
+
+#[test]
+fn run_several_days_test() {
+    let output = run(
+        &["4", "3"],
+        &[
+            "138241-674034
+",
+            "R8,U5,L5,D3
+U7,R6,D4,L4
+",
+        ],
+    );
+
+    assert!(output.status.success());
+    let rows = table_rows(&output.stdout);
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[1][..3], ["4", "1890", "1277"]);
+    assert_eq!(rows[2][..3], ["3", "6", "30"]);
+}
+
+#[test]
+fn run_all_days_test() {
+    // Without input files every day reads its own input.txt.
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc2019"))
+        .arg("all")
+        .current_dir("..")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let rows = table_rows(&output.stdout);
+    let answers: Vec<&[String]> = rows[1..].iter().map(|row| &row[..3]).collect();
+    assert_eq!(
+        answers,
+        [
+            ["1", "3426455", "5136807"],
+            ["2", "5866714", "5208"],
+            ["3", "860", "9238"],
+            ["4", "1890", "1277"],
+        ]
+    );
+}
+
+#[test]
+fn run_day_3_all_pairs_test() {
+    let output = run(
+        &["3"],
+        &["R8,U5,L5,D3
+U7,R6,D4,L4
+L1,U7,R2
+"],
+    );
+
+    // The third wire meets the second after 9 + 7 steps, as day_3 finds.
+    assert!(output.status.success());
+    assert_eq!(table_rows(&output.stdout)[1][..3], ["3", "6", "16"]);
+}
+
+#[test]
+fn run_day_3_single_wire_test() {
+    let output = run(
+        &["3"],
+        &["R8,U5,L5,D3
+"],
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Day 3: expected at least 2 wires, found 1\n"
+    );
+}
+
+#[test]
+fn input_file_count_test() {
+    let output = run(&["3", "4", "input.txt"], &[]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("expected 2 input files, found 1\n"));
+}
+
+#[test]
+fn unknown_day_test() {
+    let output = run(&["42"], &[]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "unknown day: 42\nusage: aoc2019 <day>... [input...]\n       aoc2019 all [input...]\n\
+         available days: 1, 2, 3, 4\n"
+    );
+}
+
+#[test]
+fn run_day_2_error_test() {
+    // Part one points the program at address 12, past its end.
+    let output = run(&["2"], &["1,0,0,0,99\n"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Day 2: address 12 out of range\n"
+    );
+}
+
+#[test]
+fn missing_input_file_test() {
+    // The file is gone again by the time aoc2019 looks for it.
+    let path = TempFile::new("").path().to_path_buf();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc2019"))
+        .arg("4")
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.starts_with(&format!("unable to read {}: ", path.display())));
+    assert!(!stderr.contains("panicked"));
+}
@@ -0,0 +1,68 @@
+use common::{Direction, ParseSegmentError, Vec2d};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use regex::Regex;
+
+// A wire of 301 segments, about the size of a puzzle input line.
+fn long_wire() -> String {
+    let segments = ["R75", "D30", "R83", "U83", "L12", "D49", "R71", "U7", "L72"];
+    segments
+        .iter()
+        .cycle()
+        .take(301)
+        .copied()
+        .collect::<Vec<&str>>()
+        .join(",")
+}
+
+// The regex based parser Vec2d::from_str used before, kept as the baseline.
+fn parse_with_regex(re: &Regex, s: &str) -> Result<Vec2d, ParseSegmentError> {
+    let captures = re.captures(s).unwrap();
+    let direction = &captures[1];
+    let magnitude = &captures[2];
+
+    let direction = match direction.chars().next().and_then(Direction::from_char) {
+        Some(d) => d,
+        None => return Err(ParseSegmentError::UnknownDirection(direction.to_string())),
+    };
+    if magnitude.is_empty() {
+        return Err(ParseSegmentError::MissingMagnitude);
+    }
+    if !magnitude.chars().all(|c| c.is_ascii_digit()) {
+        return Err(ParseSegmentError::InvalidMagnitude(magnitude.to_string()));
+    }
+    let magnitude = magnitude
+        .parse::<i32>()
+        .map_err(|_| ParseSegmentError::InvalidMagnitude(magnitude.to_string()))?;
+    if magnitude == 0 {
+        return Err(ParseSegmentError::ZeroMagnitude);
+    }
+
+    Ok(direction.unit().scaled(magnitude))
+}
+
+fn parse_benchmark(c: &mut Criterion) {
+    let wire = long_wire();
+    let re = Regex::new(r"(?s)^(\D?)(.*)$").unwrap();
+    let parse_all = |parse: &dyn Fn(&str) -> Result<Vec2d, ParseSegmentError>| {
+        black_box(&wire)
+            .split(',')
+            .map(parse)
+            .collect::<Result<Vec<Vec2d>, _>>()
+    };
+    assert_eq!(
+        parse_all(&|segment| parse_with_regex(&re, segment)),
+        parse_all(&|segment| segment.parse::<Vec2d>())
+    );
+
+    let mut group = c.benchmark_group("parse wire");
+    group.bench_function("regex", |bencher| {
+        bencher.iter(|| parse_all(&|segment| parse_with_regex(&re, segment)))
+    });
+    group.bench_function("hand-written", |bencher| {
+        bencher.iter(|| parse_all(&|segment| segment.parse::<Vec2d>()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse_benchmark);
+criterion_main!(benches);
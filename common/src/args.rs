@@ -0,0 +1,130 @@
+use std::env;
+use std::time::Duration;
+
+// Command line arguments shared by the days:
+//
+//     day_N [--time] [--json] [--check] [--strict] [input...]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Args {
+    pub paths: Vec<String>,
+    pub time: bool,
+    pub json: bool,
+    pub check: bool,
+    pub strict: bool,
+}
+
+impl Args {
+    // Arguments that are not flags are taken as input paths, in order.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Args {
+        let mut parsed = Args::default();
+        for arg in args {
+            if arg == "--time" {
+                parsed.time = true;
+            } else if arg == "--json" {
+                parsed.json = true;
+            } else if arg == "--check" {
+                parsed.check = true;
+            } else if arg == "--strict" {
+                parsed.strict = true;
+            } else {
+                parsed.paths.push(arg);
+            }
+        }
+        parsed
+    }
+
+    pub fn from_env() -> Args {
+        Args::parse(env::args().skip(1))
+    }
+
+    // Formats the elapsed time to be appended to an answer, or nothing when
+    // timing was not asked for.
+    pub fn timing(&self, elapsed: Duration) -> String {
+        if self.time {
+            format!(" ({:.1?})", elapsed)
+        } else {
+            String::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Args {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(parse(&[]), Args::default());
+        assert_eq!(
+            parse(&["input.txt"]),
+            Args {
+                paths: vec!["input.txt".to_string()],
+                ..Args::default()
+            }
+        );
+        assert_eq!(
+            parse(&["--time"]),
+            Args {
+                time: true,
+                ..Args::default()
+            }
+        );
+        assert_eq!(
+            parse(&["--json"]),
+            Args {
+                json: true,
+                ..Args::default()
+            }
+        );
+        assert_eq!(
+            parse(&["--check"]),
+            Args {
+                check: true,
+                ..Args::default()
+            }
+        );
+        assert_eq!(
+            parse(&["--strict"]),
+            Args {
+                strict: true,
+                ..Args::default()
+            }
+        );
+        let expected = Args {
+            paths: vec!["input.txt".to_string()],
+            time: true,
+            json: false,
+            check: false,
+            strict: false,
+        };
+        assert_eq!(parse(&["--time", "input.txt"]), expected);
+        assert_eq!(parse(&["input.txt", "--time"]), expected);
+        assert_eq!(
+            parse(&["--json", "input.txt", "--time"]),
+            Args {
+                json: true,
+                ..expected
+            }
+        );
+        assert_eq!(
+            parse(&["a.txt", "--time", "-", "b.txt"]),
+            Args {
+                paths: vec!["a.txt".to_string(), "-".to_string(), "b.txt".to_string()],
+                time: true,
+                ..Args::default()
+            }
+        );
+    }
+
+    #[test]
+    fn timing_test() {
+        let elapsed = Duration::from_micros(1234);
+
+        assert_eq!(parse(&[]).timing(elapsed), "");
+        assert_eq!(parse(&["--time"]).timing(elapsed), " (1.2ms)");
+    }
+}
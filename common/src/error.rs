@@ -0,0 +1,120 @@
+use crate::vec2d::ParseSegmentError;
+
+use std::io;
+use thiserror::Error;
+
+#[derive(Clone, Debug, PartialEq, Error)]
+pub enum RangeParseError {
+    #[error("expected two bounds separated by '-' or '..', got {0:?}")]
+    MalformedRange(String),
+    #[error("invalid bound: {0:?}")]
+    InvalidBound(String),
+    #[error("low bound {0} exceeds high bound {1}")]
+    Reversed(u32, u32),
+}
+
+// The errors the days report back to main.
+#[derive(Debug, Error)]
+pub enum AocError {
+    #[error("unable to read input: {0}")]
+    Io(#[from] io::Error),
+    #[error("invalid wire: {0}")]
+    Segment(#[from] ParseSegmentError),
+    #[error("invalid range: {0}")]
+    Range(#[from] RangeParseError),
+    #[error("expected at least {expected} {what}, found {found}")]
+    InsufficientInput {
+        what: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    #[error("no {0} found")]
+    NotFound(&'static str),
+    #[error("{0} overflowed")]
+    Overflow(&'static str),
+    #[error("wire {second} duplicates wire {first}")]
+    DuplicateWire { first: usize, second: usize },
+    #[error("illegal opcode {opcode} at address {address}")]
+    IllegalOpcode { opcode: usize, address: usize },
+    #[error("address {0} out of range")]
+    AddressOutOfRange(usize),
+    #[error("line {line}: {source}")]
+    Line { line: usize, source: Box<AocError> },
+}
+
+impl AocError {
+    // Attaches the 1-based number of the input line the error was found on.
+    pub fn at_line(self, line: usize) -> AocError {
+        AocError::Line {
+            line,
+            source: Box::new(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_test() {
+        assert_eq!(
+            AocError::from(ParseSegmentError::MissingMagnitude).to_string(),
+            "invalid wire: missing magnitude"
+        );
+        assert_eq!(
+            AocError::from(RangeParseError::Reversed(5, 3)).to_string(),
+            "invalid range: low bound 5 exceeds high bound 3"
+        );
+        assert_eq!(
+            AocError::InsufficientInput {
+                what: "wires",
+                expected: 2,
+                found: 1
+            }
+            .to_string(),
+            "expected at least 2 wires, found 1"
+        );
+        assert_eq!(
+            AocError::NotFound("intersections").to_string(),
+            "no intersections found"
+        );
+        assert_eq!(
+            AocError::Overflow("combined steps").to_string(),
+            "combined steps overflowed"
+        );
+        assert_eq!(
+            AocError::DuplicateWire {
+                first: 1,
+                second: 2
+            }
+            .to_string(),
+            "wire 2 duplicates wire 1"
+        );
+        assert_eq!(
+            AocError::IllegalOpcode {
+                opcode: 42,
+                address: 4
+            }
+            .to_string(),
+            "illegal opcode 42 at address 4"
+        );
+        assert_eq!(
+            AocError::AddressOutOfRange(12).to_string(),
+            "address 12 out of range"
+        );
+        assert_eq!(
+            AocError::from(RangeParseError::InvalidBound("x".to_string()))
+                .at_line(3)
+                .to_string(),
+            "line 3: invalid range: invalid bound: \"x\""
+        );
+    }
+
+    #[test]
+    fn from_io_error_test() {
+        let e = AocError::from(io::Error::new(io::ErrorKind::NotFound, "gone"));
+        assert!(matches!(e, AocError::Io(ref e) if e.kind() == io::ErrorKind::NotFound));
+        assert_eq!(e.to_string(), "unable to read input: gone");
+    }
+}
@@ -0,0 +1,165 @@
+use crate::args::Args;
+
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::io::BufReader;
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+#[cfg(feature = "gzip")]
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+// Decompresses files that start like a gzip stream, whatever they are named,
+// and passes everything else through as is.
+#[cfg(feature = "gzip")]
+fn decompress(mut reader: BufReader<File>) -> io::Result<Box<dyn BufRead>> {
+    if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        let decoder = flate2::bufread::MultiGzDecoder::new(reader);
+        Ok(Box::new(BufReader::new(decoder)))
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+#[cfg(not(feature = "gzip"))]
+fn decompress(reader: BufReader<File>) -> io::Result<Box<dyn BufRead>> {
+    Ok(Box::new(reader))
+}
+
+// Reads all lines from the file at path, or from stdin when there is no path.
+// A byte order mark left at the start by some editors is dropped. With the
+// gzip feature, gzip-compressed files are decompressed on the fly.
+pub fn read_lines(path: Option<&str>) -> io::Result<Vec<String>> {
+    let mut lines = match path {
+        Some(path) => decompress(BufReader::new(File::open(path)?))?
+            .lines()
+            .collect::<io::Result<Vec<String>>>()?,
+        None => io::stdin().lock().lines().collect::<io::Result<_>>()?,
+    };
+    if let Some(first) = lines.first_mut() {
+        if first.starts_with(BYTE_ORDER_MARK) {
+            first.remove(0);
+        }
+    }
+    Ok(lines)
+}
+
+// The path standing for stdin among the input paths.
+pub const STDIN_PATH: &str = "-";
+
+// Reads the lines of every input in turn, one after the other, where "-" reads
+// from stdin. Without any paths only stdin is read. Errors mention the file
+// that could not be read.
+pub fn read_lines_from_all(paths: &[String]) -> io::Result<Vec<String>> {
+    if paths.is_empty() {
+        return read_lines(None);
+    }
+    let mut lines = Vec::new();
+    for path in paths {
+        if path == STDIN_PATH {
+            lines.extend(read_lines(None)?);
+        } else {
+            let read = read_lines(Some(path))
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path, e)))?;
+            lines.extend(read);
+        }
+    }
+    Ok(lines)
+}
+
+// Reads all lines from the files given as arguments, or from stdin when no
+// file is given.
+pub fn read_lines_from_stdin_or_arg() -> io::Result<Vec<String>> {
+    read_lines_from_all(&Args::from_env().paths)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn read_lines_test() {
+        let path = env::temp_dir().join("common_read_lines_test.txt");
+        fs::write(&path, "R8,U5,L5,D3\nU7,R6,D4,L4\n").unwrap();
+
+        let lines = read_lines(path.to_str());
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            lines.unwrap(),
+            vec!["R8,U5,L5,D3".to_string(), "U7,R6,D4,L4".to_string()]
+        );
+    }
+
+    #[test]
+    fn read_lines_byte_order_mark_test() {
+        let path = env::temp_dir().join("common_read_lines_byte_order_mark_test.txt");
+        fs::write(&path, b"\xef\xbb\xbfR8,U5,L5,D3\nU7,R6,D4,L4\n").unwrap();
+
+        let lines = read_lines(path.to_str());
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            lines.unwrap(),
+            vec!["R8,U5,L5,D3".to_string(), "U7,R6,D4,L4".to_string()]
+        );
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn read_lines_gzip_test() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"R8,U5,L5,D3\nU7,R6,D4,L4\n").unwrap();
+        let path = env::temp_dir().join("common_read_lines_gzip_test.txt.gz");
+        fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+        let lines = read_lines(path.to_str());
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            lines.unwrap(),
+            vec!["R8,U5,L5,D3".to_string(), "U7,R6,D4,L4".to_string()]
+        );
+    }
+
+    #[test]
+    fn read_lines_from_all_test() {
+        let first = env::temp_dir().join("common_read_lines_from_all_test_1.txt");
+        let second = env::temp_dir().join("common_read_lines_from_all_test_2.txt");
+        fs::write(&first, "R8,U5,L5,D3\n").unwrap();
+        fs::write(&second, "U7,R6,D4,L4\n").unwrap();
+        let paths: Vec<String> = [&first, &second]
+            .iter()
+            .map(|path| path.to_str().unwrap().to_string())
+            .collect();
+
+        let lines = read_lines_from_all(&paths);
+        let missing = read_lines_from_all(&[paths[0].clone(), "missing.txt".to_string()]);
+        fs::remove_file(&first).unwrap();
+        fs::remove_file(&second).unwrap();
+
+        assert_eq!(
+            lines.unwrap(),
+            vec!["R8,U5,L5,D3".to_string(), "U7,R6,D4,L4".to_string()]
+        );
+        let e = missing.unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::NotFound);
+        assert!(e.to_string().starts_with("missing.txt: "));
+    }
+
+    #[test]
+    fn read_lines_missing_file_test() {
+        let path = env::temp_dir().join("common_read_lines_missing_file_test.txt");
+
+        assert_eq!(
+            read_lines(path.to_str()).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
@@ -0,0 +1,10 @@
+// Code shared between the days.
+
+pub mod args;
+mod error;
+pub mod input;
+pub mod report;
+mod vec2d;
+
+pub use error::{AocError, RangeParseError};
+pub use vec2d::{parse_step, ByDistance, Direction, ParseSegmentError, Vec2d, WideVec2d};
@@ -0,0 +1,118 @@
+use std::fmt;
+use std::time::Duration;
+
+// The answers of a day, formatted and ready to be printed. Keeping the
+// formatting out of the solutions lets every day print the same way.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    pub part1: String,
+    pub part2: String,
+}
+
+impl Report {
+    pub fn print(&self) {
+        println!("{}", self);
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Part 1: {}\nPart 2: {}", self.part1, self.part2)
+    }
+}
+
+// The answers of a day together with how long it took to find them, as
+// collected by the runner.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DayAnswers {
+    pub day: u8,
+    pub part1: String,
+    pub part2: String,
+    pub elapsed: Duration,
+}
+
+impl DayAnswers {
+    // Lays out the answers as a table with one row per day, every column right
+    // aligned to its widest entry.
+    pub fn table(answers: &[DayAnswers]) -> String {
+        let header = ["Day", "Part 1", "Part 2", "Time"];
+        let rows: Vec<[String; 4]> = answers
+            .iter()
+            .map(|answers| {
+                [
+                    answers.day.to_string(),
+                    answers.part1.clone(),
+                    answers.part2.clone(),
+                    format!("{:.1?}", answers.elapsed),
+                ]
+            })
+            .collect();
+
+        // Widths count characters rather than bytes, as in "µs".
+        let mut widths = header.map(|cell| cell.chars().count());
+        for row in rows.iter() {
+            for (width, cell) in widths.iter_mut().zip(row.iter()) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let mut table = String::new();
+        let header = header.map(str::to_string);
+        for row in std::iter::once(&header).chain(rows.iter()) {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+                .collect();
+            table.push_str(&cells.join("  "));
+            table.push('\n');
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn day_answers_table_test() {
+        let answers = [
+            DayAnswers {
+                day: 1,
+                part1: "3267890".to_string(),
+                part2: "4898972".to_string(),
+                elapsed: Duration::from_micros(120),
+            },
+            DayAnswers {
+                day: 4,
+                part1: "1890".to_string(),
+                part2: "1277".to_string(),
+                elapsed: Duration::from_micros(1234),
+            },
+        ];
+
+        assert_eq!(
+            DayAnswers::table(&answers),
+            "\
+Day   Part 1   Part 2     Time
+  1  3267890  4898972  120.0µs
+  4     1890     1277    1.2ms
+"
+        );
+        assert_eq!(DayAnswers::table(&[]), "Day  Part 1  Part 2  Time\n");
+    }
+
+    #[test]
+    fn display_test() {
+        let report = Report {
+            part1: "distance: 159".to_string(),
+            part2: "steps: 610".to_string(),
+        };
+
+        assert_eq!(
+            report.to_string().lines().collect::<Vec<&str>>(),
+            vec!["Part 1: distance: 159", "Part 2: steps: 610"]
+        );
+    }
+}
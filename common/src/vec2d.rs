@@ -0,0 +1,765 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, AddAssign, Neg, Sub};
+use std::str::FromStr;
+
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vec2d {
+    pub x: i32,
+    pub y: i32,
+}
+
+// Same as Vec2d but with i64 components, for wires too long for i32 to hold
+// their coordinates without overflowing.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WideVec2d {
+    pub x: i64,
+    pub y: i64,
+}
+
+// Generates the constructors, Manhattan distance, scaling and arithmetic
+// operators Vec2d and WideVec2d share, which differ only in their coordinate
+// type.
+macro_rules! vector {
+    ($name:ident, $coordinate:ty) => {
+        impl $name {
+            pub const ORIGIN: $name = $name { x: 0, y: 0 };
+
+            pub const fn new(x: $coordinate, y: $coordinate) -> $name {
+                $name { x, y }
+            }
+
+            pub fn manhattan_distance(self) -> $coordinate {
+                self.x.abs() + self.y.abs()
+            }
+
+            pub fn manhattan_distance_to(self, other: $name) -> $coordinate {
+                (self - other).manhattan_distance()
+            }
+
+            pub fn scaled(self, k: $coordinate) -> $name {
+                $name {
+                    x: self.x * k,
+                    y: self.y * k,
+                }
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+
+            fn add(self, other: $name) -> Self {
+                $name {
+                    x: self.x + other.x,
+                    y: self.y + other.y,
+                }
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, other: $name) {
+                *self = *self + other;
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+
+            fn sub(self, other: $name) -> Self {
+                $name {
+                    x: self.x - other.x,
+                    y: self.y - other.y,
+                }
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> Self {
+                $name {
+                    x: -self.x,
+                    y: -self.y,
+                }
+            }
+        }
+    };
+}
+
+vector!(Vec2d, i32);
+vector!(WideVec2d, i64);
+
+impl Vec2d {
+    pub fn euclidean_distance(self) -> f64 {
+        f64::from(self.x).hypot(f64::from(self.y))
+    }
+
+    pub fn euclidean_distance_to(self, other: Vec2d) -> f64 {
+        (self - other).euclidean_distance()
+    }
+
+    pub fn dot(self, other: Vec2d) -> i32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    // The z component of the cross product. Positive when other is a left turn
+    // from self, negative when it is a right turn and zero when they are
+    // parallel.
+    pub fn cross(self, other: Vec2d) -> i32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    // The displacement to other divided by the greatest common divisor of its
+    // components, so that all points on a line of sight from self share the
+    // same direction. Identical points give the zero vector.
+    pub fn reduced_direction(self, other: Vec2d) -> Vec2d {
+        let d = other - self;
+        match gcd(d.x.abs(), d.y.abs()) {
+            0 => d,
+            divisor => Vec2d {
+                x: d.x / divisor,
+                y: d.y / divisor,
+            },
+        }
+    }
+
+    // The sign of each component: -1, 0 or 1. For an axis-aligned vector this
+    // is the unit vector pointing the same way.
+    pub fn signum(self) -> Vec2d {
+        Vec2d {
+            x: self.x.signum(),
+            y: self.y.signum(),
+        }
+    }
+
+    // Turns the vector a quarter turn counterclockwise, with +y pointing up.
+    pub fn rotate_left(self) -> Vec2d {
+        Vec2d {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    // Turns the vector a quarter turn clockwise, with +y pointing up.
+    pub fn rotate_right(self) -> Vec2d {
+        Vec2d {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+
+    // The orthogonally adjacent points, clockwise starting from the one above.
+    pub fn neighbors4(self) -> [Vec2d; 4] {
+        [
+            self + Vec2d { x: 0, y: 1 },
+            self + Vec2d { x: 1, y: 0 },
+            self + Vec2d { x: 0, y: -1 },
+            self + Vec2d { x: -1, y: 0 },
+        ]
+    }
+
+    // The orthogonally and diagonally adjacent points, clockwise starting from
+    // the one above.
+    pub fn neighbors8(self) -> [Vec2d; 8] {
+        [
+            self + Vec2d { x: 0, y: 1 },
+            self + Vec2d { x: 1, y: 1 },
+            self + Vec2d { x: 1, y: 0 },
+            self + Vec2d { x: 1, y: -1 },
+            self + Vec2d { x: 0, y: -1 },
+            self + Vec2d { x: -1, y: -1 },
+            self + Vec2d { x: -1, y: 0 },
+            self + Vec2d { x: -1, y: 1 },
+        ]
+    }
+
+    // Splits an axis-aligned, non-zero segment into its direction and length.
+    pub fn as_step(self) -> Option<(Direction, i32)> {
+        match (self.x, self.y) {
+            (0, y) if y > 0 => Some((Direction::Up, y)),
+            (0, y) if y < 0 => Some((Direction::Down, -y)),
+            (x, 0) if x < 0 => Some((Direction::Left, -x)),
+            (x, 0) if x > 0 => Some((Direction::Right, x)),
+            _ => None,
+        }
+    }
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl From<(i32, i32)> for Vec2d {
+    fn from((x, y): (i32, i32)) -> Vec2d {
+        Vec2d { x, y }
+    }
+}
+
+impl From<Vec2d> for (i32, i32) {
+    fn from(v: Vec2d) -> (i32, i32) {
+        (v.x, v.y)
+    }
+}
+
+impl From<Vec2d> for WideVec2d {
+    fn from(v: Vec2d) -> WideVec2d {
+        WideVec2d {
+            x: i64::from(v.x),
+            y: i64::from(v.y),
+        }
+    }
+}
+
+// Orders points by their Manhattan distance from the origin, breaking ties on
+// x and then y so that the order is total.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct ByDistance(pub Vec2d);
+
+impl ByDistance {
+    fn key(&self) -> (i32, i32, i32) {
+        (self.0.manhattan_distance(), self.0.x, self.0.y)
+    }
+}
+
+impl Ord for ByDistance {
+    fn cmp(&self, other: &ByDistance) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl PartialOrd for ByDistance {
+    fn partial_cmp(&self, other: &ByDistance) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn unit(self) -> Vec2d {
+        match self {
+            Direction::Up => Vec2d { x: 0, y: 1 },
+            Direction::Down => Vec2d { x: 0, y: -1 },
+            Direction::Left => Vec2d { x: -1, y: 0 },
+            Direction::Right => Vec2d { x: 1, y: 0 },
+        }
+    }
+
+    // Accepts both upper and lower case letters.
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c.to_ascii_uppercase() {
+            'U' => Some(Direction::Up),
+            'D' => Some(Direction::Down),
+            'L' => Some(Direction::Left),
+            'R' => Some(Direction::Right),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Direction::Up => 'U',
+            Direction::Down => 'D',
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseSegmentError {
+    UnknownDirection(String),
+    MissingMagnitude,
+    InvalidMagnitude(String),
+    ZeroMagnitude,
+}
+
+impl fmt::Display for ParseSegmentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseSegmentError::UnknownDirection(d) => write!(f, "unknown direction: {:?}", d),
+            ParseSegmentError::MissingMagnitude => write!(f, "missing magnitude"),
+            ParseSegmentError::InvalidMagnitude(m) => write!(f, "invalid magnitude: {:?}", m),
+            ParseSegmentError::ZeroMagnitude => write!(f, "zero magnitude"),
+        }
+    }
+}
+
+impl Error for ParseSegmentError {}
+
+// Parses a segment like "R8" into its direction and length.
+pub fn parse_step(s: &str) -> Result<(Direction, i32), ParseSegmentError> {
+    parse_segment(s)
+}
+
+// Same as parse_step for any integer type wide enough to hold the length.
+fn parse_segment<T>(s: &str) -> Result<(Direction, T), ParseSegmentError>
+where
+    T: FromStr + Default + PartialEq,
+{
+    // The first character is the direction, unless it is a digit, in which
+    // case the direction is missing and all of it is the magnitude.
+    let split = match s.chars().next() {
+        Some(c) if !c.is_ascii_digit() => c.len_utf8(),
+        _ => 0,
+    };
+    let (direction, magnitude) = s.split_at(split);
+
+    let direction = match direction.chars().next().and_then(Direction::from_char) {
+        Some(d) => d,
+        None => return Err(ParseSegmentError::UnknownDirection(direction.to_string())),
+    };
+    if magnitude.is_empty() {
+        return Err(ParseSegmentError::MissingMagnitude);
+    }
+    if !magnitude.chars().all(|c| c.is_ascii_digit()) {
+        return Err(ParseSegmentError::InvalidMagnitude(magnitude.to_string()));
+    }
+    let magnitude = magnitude
+        .parse::<T>()
+        .map_err(|_| ParseSegmentError::InvalidMagnitude(magnitude.to_string()))?;
+    if magnitude == T::default() {
+        return Err(ParseSegmentError::ZeroMagnitude);
+    }
+
+    Ok((direction, magnitude))
+}
+
+impl FromStr for Vec2d {
+    type Err = ParseSegmentError;
+
+    fn from_str(s: &str) -> Result<Vec2d, ParseSegmentError> {
+        let (direction, magnitude) = parse_step(s)?;
+        Ok(direction.unit().scaled(magnitude))
+    }
+}
+
+impl FromStr for WideVec2d {
+    type Err = ParseSegmentError;
+
+    fn from_str(s: &str) -> Result<WideVec2d, ParseSegmentError> {
+        let (direction, magnitude) = parse_segment(s)?;
+        Ok(WideVec2d::from(direction.unit()).scaled(magnitude))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn point_from_str_test() {
+        let test_cases = [
+            ("R8", Vec2d { x: 8, y: 0 }),
+            ("U5", Vec2d { x: 0, y: 5 }),
+            ("L5", Vec2d { x: -5, y: 0 }),
+            ("D3", Vec2d { x: 0, y: -3 }),
+        ];
+        for case in test_cases.iter() {
+            assert_eq!(case.0.parse::<Vec2d>(), Ok(case.1));
+        }
+    }
+
+    #[test]
+    fn point_from_str_error_test() {
+        let test_cases = [
+            ("X5", ParseSegmentError::UnknownDirection("X".to_string())),
+            ("5", ParseSegmentError::UnknownDirection("".to_string())),
+            ("", ParseSegmentError::UnknownDirection("".to_string())),
+            ("é5", ParseSegmentError::UnknownDirection("é".to_string())),
+            ("RR5", ParseSegmentError::InvalidMagnitude("R5".to_string())),
+            ("R+5", ParseSegmentError::InvalidMagnitude("+5".to_string())),
+            (
+                "R5\n",
+                ParseSegmentError::InvalidMagnitude("5\n".to_string()),
+            ),
+            ("R 5", ParseSegmentError::InvalidMagnitude(" 5".to_string())),
+            ("R", ParseSegmentError::MissingMagnitude),
+            ("R0", ParseSegmentError::ZeroMagnitude),
+            ("u000", ParseSegmentError::ZeroMagnitude),
+            ("R-3", ParseSegmentError::InvalidMagnitude("-3".to_string())),
+            ("U1x", ParseSegmentError::InvalidMagnitude("1x".to_string())),
+            (
+                "L99999999999",
+                ParseSegmentError::InvalidMagnitude("99999999999".to_string()),
+            ),
+        ];
+        for case in test_cases.iter() {
+            assert_eq!(case.0.parse::<Vec2d>(), Err(case.1.clone()));
+        }
+    }
+
+    #[test]
+    fn direction_unit_test() {
+        let test_cases = [
+            (Direction::Up, Vec2d { x: 0, y: 1 }),
+            (Direction::Down, Vec2d { x: 0, y: -1 }),
+            (Direction::Left, Vec2d { x: -1, y: 0 }),
+            (Direction::Right, Vec2d { x: 1, y: 0 }),
+        ];
+        for case in test_cases.iter() {
+            assert_eq!(case.0.unit(), case.1);
+        }
+    }
+
+    #[test]
+    fn direction_from_char_test() {
+        assert_eq!(Direction::from_char('U'), Some(Direction::Up));
+        assert_eq!(Direction::from_char('D'), Some(Direction::Down));
+        assert_eq!(Direction::from_char('L'), Some(Direction::Left));
+        assert_eq!(Direction::from_char('R'), Some(Direction::Right));
+        assert_eq!(Direction::from_char('u'), Some(Direction::Up));
+        assert_eq!(Direction::from_char('d'), Some(Direction::Down));
+        assert_eq!(Direction::from_char('l'), Some(Direction::Left));
+        assert_eq!(Direction::from_char('r'), Some(Direction::Right));
+        assert_eq!(Direction::from_char('X'), None);
+        assert_eq!(Direction::from_char('x'), None);
+    }
+
+    #[test]
+    fn step_round_trip_test() {
+        for segment in ["R8", "U5", "L5", "D3"].iter() {
+            let (direction, magnitude) = parse_step(segment).unwrap();
+            assert_eq!(format!("{}{}", direction, magnitude), *segment);
+
+            let vertex = segment.parse::<Vec2d>().unwrap();
+            assert_eq!(vertex.as_step(), Some((direction, magnitude)));
+        }
+        assert_eq!(Vec2d { x: 0, y: 0 }.as_step(), None);
+        assert_eq!(Vec2d { x: 1, y: 1 }.as_step(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let point = Vec2d { x: -3, y: 7 };
+        let json = serde_json::to_string(&point).unwrap();
+
+        assert_eq!(json, r#"{"x":-3,"y":7}"#);
+        assert_eq!(serde_json::from_str::<Vec2d>(&json).unwrap(), point);
+    }
+
+    #[test]
+    fn wide_point_test() {
+        // Each segment fits in an i32, but the wire ends past i32::MAX.
+        let segments = ["R2147483647", "U5", "R2147483647", "L3"];
+        assert!(segments.iter().all(|s| s.parse::<Vec2d>().is_ok()));
+        let x = segments
+            .iter()
+            .map(|s| s.parse::<Vec2d>().unwrap().x)
+            .try_fold(0i32, |sum, x| sum.checked_add(x));
+        assert_eq!(x, None);
+
+        let end = segments
+            .iter()
+            .map(|s| s.parse::<WideVec2d>().unwrap())
+            .fold(WideVec2d::ORIGIN, |end, segment| end + segment);
+        assert_eq!(end, WideVec2d::new(4_294_967_291, 5));
+        assert_eq!(end.manhattan_distance(), 4_294_967_296);
+        assert_eq!(
+            end.manhattan_distance_to(WideVec2d::new(4_294_967_291, -5)),
+            10
+        );
+        assert_eq!(-end - end, end.scaled(-2));
+
+        // A single length past i32::MAX is rejected by Vec2d but fits in a
+        // WideVec2d.
+        assert_eq!(
+            "D3000000000".parse::<Vec2d>(),
+            Err(ParseSegmentError::InvalidMagnitude(
+                "3000000000".to_string()
+            ))
+        );
+        assert_eq!(
+            "D3000000000".parse::<WideVec2d>(),
+            Ok(WideVec2d::new(0, -3_000_000_000))
+        );
+        assert_eq!(
+            "L0".parse::<WideVec2d>(),
+            Err(ParseSegmentError::ZeroMagnitude)
+        );
+        assert_eq!(WideVec2d::from(Vec2d::new(-3, 7)), WideVec2d::new(-3, 7));
+    }
+
+    #[test]
+    fn new_test() {
+        const ORIGIN: Vec2d = Vec2d::new(0, 0);
+        const POINT: Vec2d = Vec2d::new(-3, 7);
+
+        assert_eq!(ORIGIN, Vec2d { x: 0, y: 0 });
+        assert_eq!(POINT, Vec2d { x: -3, y: 7 });
+    }
+
+    #[test]
+    fn origin_test() {
+        assert_eq!(Vec2d::default(), Vec2d::ORIGIN);
+        assert_eq!(Vec2d::ORIGIN, Vec2d { x: 0, y: 0 });
+        assert_eq!(Vec2d::ORIGIN.manhattan_distance(), 0);
+    }
+
+    #[test]
+    fn tuple_conversion_test() {
+        let point = Vec2d { x: -3, y: 7 };
+
+        assert_eq!(Vec2d::from((-3, 7)), point);
+        assert_eq!(<(i32, i32)>::from(point), (-3, 7));
+
+        let tuple: (i32, i32) = point.into();
+        let back: Vec2d = tuple.into();
+        assert_eq!(back, point);
+    }
+
+    #[test]
+    fn by_distance_heap_test() {
+        use std::cmp::Reverse;
+        use std::collections::BinaryHeap;
+
+        let points = [
+            Vec2d::new(5, -5),
+            Vec2d::new(0, 1),
+            Vec2d::new(-3, 2),
+            Vec2d::new(1, 0),
+            Vec2d::new(2, -3),
+            Vec2d::new(0, 0),
+            Vec2d::new(-1, 0),
+        ];
+        let mut heap: BinaryHeap<Reverse<ByDistance>> =
+            points.iter().map(|p| Reverse(ByDistance(*p))).collect();
+
+        let mut popped = Vec::new();
+        while let Some(Reverse(ByDistance(point))) = heap.pop() {
+            popped.push(point);
+        }
+        assert_eq!(
+            popped,
+            vec![
+                Vec2d::new(0, 0),
+                Vec2d::new(-1, 0),
+                Vec2d::new(0, 1),
+                Vec2d::new(1, 0),
+                Vec2d::new(-3, 2),
+                Vec2d::new(2, -3),
+                Vec2d::new(5, -5),
+            ]
+        );
+    }
+
+    #[test]
+    fn add_points_test() {
+        let a = Vec2d { x: 7, y: 3 };
+        let b = Vec2d { x: 3, y: 7 };
+        let expected = Vec2d { x: 10, y: 10 };
+
+        assert_eq!(a + b, expected);
+    }
+
+    #[test]
+    fn add_assign_points_test() {
+        let mut a = Vec2d { x: 7, y: -3 };
+        a += Vec2d { x: -10, y: 1 };
+
+        assert_eq!(a, Vec2d { x: -3, y: -2 });
+    }
+
+    #[test]
+    fn sub_points_test() {
+        let a = Vec2d { x: 7, y: -3 };
+        let b = Vec2d { x: -3, y: 7 };
+
+        assert_eq!(a - b, Vec2d { x: 10, y: -10 });
+        assert_eq!(b - a, Vec2d { x: -10, y: 10 });
+    }
+
+    #[test]
+    fn neg_point_test() {
+        assert_eq!(-Vec2d { x: 7, y: -3 }, Vec2d { x: -7, y: 3 });
+        assert_eq!(-Vec2d { x: 0, y: 0 }, Vec2d { x: 0, y: 0 });
+    }
+
+    #[test]
+    fn scaled_point_test() {
+        let a = Vec2d { x: 2, y: -3 };
+
+        assert_eq!(a.scaled(4), Vec2d { x: 8, y: -12 });
+        assert_eq!(a.scaled(-1), -a);
+        assert_eq!(a.scaled(0), Vec2d { x: 0, y: 0 });
+    }
+
+    #[test]
+    fn manhattan_distance_to_test() {
+        let a = Vec2d { x: -2, y: 3 };
+        let b = Vec2d { x: 4, y: -5 };
+
+        assert_eq!(a.manhattan_distance_to(b), 14);
+        assert_eq!(b.manhattan_distance_to(a), 14);
+        assert_eq!(a.manhattan_distance_to(a), 0);
+        assert_eq!(a.manhattan_distance_to(Vec2d { x: 0, y: 0 }), 5);
+    }
+
+    #[test]
+    fn euclidean_distance_test() {
+        assert_eq!(Vec2d { x: 3, y: 4 }.euclidean_distance(), 5.0);
+        assert_eq!(Vec2d { x: -3, y: -4 }.euclidean_distance(), 5.0);
+        assert_eq!(Vec2d { x: 0, y: 0 }.euclidean_distance(), 0.0);
+
+        let a = Vec2d { x: -2, y: 3 };
+        let b = Vec2d { x: 4, y: -5 };
+        assert_eq!(a.euclidean_distance_to(b), 10.0);
+        assert_eq!(b.euclidean_distance_to(a), 10.0);
+    }
+
+    #[test]
+    fn dot_test() {
+        let a = Vec2d { x: 2, y: 3 };
+
+        assert_eq!(a.dot(a.rotate_left()), 0);
+        assert_eq!(a.dot(a.rotate_right()), 0);
+        assert_eq!(Direction::Up.unit().dot(Direction::Right.unit()), 0);
+        assert_eq!(a.dot(Vec2d { x: 4, y: -1 }), 5);
+        assert_eq!(a.dot(-a), -13);
+    }
+
+    #[test]
+    fn cross_test() {
+        // Walking from p to q and then on to r.
+        let turn = |p: Vec2d, q: Vec2d, r: Vec2d| (q - p).cross(r - q);
+        let (p, q) = (Vec2d { x: 0, y: 0 }, Vec2d { x: 2, y: 1 });
+
+        assert!(turn(p, q, Vec2d { x: 3, y: 3 }) > 0);
+        assert!(turn(p, q, Vec2d { x: 4, y: 0 }) < 0);
+        assert_eq!(turn(p, q, Vec2d { x: 6, y: 3 }), 0);
+
+        let a = Vec2d { x: 2, y: 3 };
+        assert_eq!(a.cross(a.rotate_left()), 13);
+        assert_eq!(a.cross(a.rotate_right()), -13);
+        assert_eq!(a.cross(a.scaled(-2)), 0);
+    }
+
+    #[test]
+    fn reduced_direction_test() {
+        let origin = Vec2d::new(0, 0);
+
+        assert_eq!(origin.reduced_direction(Vec2d::new(4, 2)), Vec2d::new(2, 1));
+        assert_eq!(origin.reduced_direction(Vec2d::new(2, 1)), Vec2d::new(2, 1));
+        assert_eq!(
+            origin.reduced_direction(Vec2d::new(0, -3)),
+            Vec2d::new(0, -1)
+        );
+        assert_eq!(
+            origin.reduced_direction(Vec2d::new(-6, 9)),
+            Vec2d::new(-2, 3)
+        );
+        assert_eq!(origin.reduced_direction(origin), origin);
+
+        let a = Vec2d::new(3, -1);
+        assert_eq!(a.reduced_direction(Vec2d::new(7, 7)), Vec2d::new(1, 2));
+        assert_eq!(a.reduced_direction(a), origin);
+    }
+
+    #[test]
+    fn signum_test() {
+        assert_eq!(Vec2d::new(7, 3).signum(), Vec2d::new(1, 1));
+        assert_eq!(Vec2d::new(-7, -3).signum(), Vec2d::new(-1, -1));
+        assert_eq!(Vec2d::new(5, -2).signum(), Vec2d::new(1, -1));
+        assert_eq!(Vec2d::new(0, 4).signum(), Direction::Up.unit());
+        assert_eq!(Vec2d::new(-4, 0).signum(), Direction::Left.unit());
+        assert_eq!(Vec2d::ORIGIN.signum(), Vec2d::ORIGIN);
+    }
+
+    #[test]
+    fn rotate_test() {
+        let (up, down, left, right) = (
+            Direction::Up.unit(),
+            Direction::Down.unit(),
+            Direction::Left.unit(),
+            Direction::Right.unit(),
+        );
+
+        assert_eq!(up.rotate_left(), left);
+        assert_eq!(left.rotate_left(), down);
+        assert_eq!(down.rotate_left(), right);
+        assert_eq!(right.rotate_left(), up);
+
+        assert_eq!(up.rotate_right(), right);
+        assert_eq!(right.rotate_right(), down);
+        assert_eq!(down.rotate_right(), left);
+        assert_eq!(left.rotate_right(), up);
+
+        let a = Vec2d { x: 3, y: -7 };
+        assert_eq!(a.rotate_left().rotate_right(), a);
+        assert_eq!(a.rotate_left().rotate_left(), -a);
+        assert_eq!(
+            a.rotate_right()
+                .rotate_right()
+                .rotate_right()
+                .rotate_right(),
+            a
+        );
+    }
+
+    #[test]
+    fn neighbors4_test() {
+        assert_eq!(
+            Vec2d { x: 0, y: 0 }.neighbors4(),
+            [
+                Vec2d { x: 0, y: 1 },
+                Vec2d { x: 1, y: 0 },
+                Vec2d { x: 0, y: -1 },
+                Vec2d { x: -1, y: 0 },
+            ]
+        );
+        assert_eq!(
+            Vec2d { x: -3, y: -5 }.neighbors4(),
+            [
+                Vec2d { x: -3, y: -4 },
+                Vec2d { x: -2, y: -5 },
+                Vec2d { x: -3, y: -6 },
+                Vec2d { x: -4, y: -5 },
+            ]
+        );
+    }
+
+    #[test]
+    fn neighbors8_test() {
+        assert_eq!(
+            Vec2d { x: 0, y: 0 }.neighbors8(),
+            [
+                Vec2d { x: 0, y: 1 },
+                Vec2d { x: 1, y: 1 },
+                Vec2d { x: 1, y: 0 },
+                Vec2d { x: 1, y: -1 },
+                Vec2d { x: 0, y: -1 },
+                Vec2d { x: -1, y: -1 },
+                Vec2d { x: -1, y: 0 },
+                Vec2d { x: -1, y: 1 },
+            ]
+        );
+
+        let point = Vec2d { x: -3, y: -5 };
+        let neighbors = point.neighbors8();
+        for neighbor in neighbors.iter() {
+            assert_ne!(*neighbor, point);
+            assert!((neighbor.x - point.x).abs() <= 1 && (neighbor.y - point.y).abs() <= 1);
+        }
+        for neighbor in point.neighbors4().iter() {
+            assert!(neighbors.contains(neighbor));
+        }
+        assert_eq!(neighbors[5], Vec2d { x: -4, y: -6 });
+    }
+}
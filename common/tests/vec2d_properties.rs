@@ -0,0 +1,67 @@
+use common::{Direction, Vec2d};
+use proptest::prelude::*;
+
+// Components are kept small enough that sums and negations never overflow.
+fn vec2d() -> impl Strategy<Value = Vec2d> {
+    (-1_000_000..=1_000_000, -1_000_000..=1_000_000).prop_map(|(x, y)| Vec2d { x, y })
+}
+
+fn direction() -> impl Strategy<Value = Direction> {
+    prop_oneof![
+        Just(Direction::Up),
+        Just(Direction::Down),
+        Just(Direction::Left),
+        Just(Direction::Right),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn add_commutes(a in vec2d(), b in vec2d()) {
+        prop_assert_eq!(a + b, b + a);
+    }
+
+    #[test]
+    fn add_associates(a in vec2d(), b in vec2d(), c in vec2d()) {
+        prop_assert_eq!((a + b) + c, a + (b + c));
+    }
+
+    #[test]
+    fn sub_undoes_add(a in vec2d(), b in vec2d()) {
+        prop_assert_eq!((a + b) - b, a);
+    }
+
+    #[test]
+    fn add_neg_is_origin(a in vec2d()) {
+        prop_assert_eq!(a + (-a), Vec2d { x: 0, y: 0 });
+        prop_assert_eq!(a - a, Vec2d { x: 0, y: 0 });
+    }
+
+    #[test]
+    fn add_assign_matches_add(a in vec2d(), b in vec2d()) {
+        let mut c = a;
+        c += b;
+        prop_assert_eq!(c, a + b);
+    }
+
+    #[test]
+    fn manhattan_distance_sums_magnitudes(a in vec2d()) {
+        prop_assert_eq!(a.manhattan_distance(), a.x.abs() + a.y.abs());
+        prop_assert_eq!(a.manhattan_distance(), (-a).manhattan_distance());
+    }
+
+    #[test]
+    fn manhattan_distance_to_is_symmetric(a in vec2d(), b in vec2d()) {
+        prop_assert_eq!(a.manhattan_distance_to(b), b.manhattan_distance_to(a));
+        prop_assert_eq!(a.manhattan_distance_to(b), (a - b).manhattan_distance());
+    }
+
+    #[test]
+    fn segment_parses_to_scaled_unit(direction in direction(), magnitude in 1..=i32::MAX) {
+        let segment = format!("{}{}", direction, magnitude);
+        prop_assert_eq!(segment.parse::<Vec2d>(), Ok(direction.unit().scaled(magnitude)));
+
+        let padded = format!("{}{:08}", direction, magnitude);
+        prop_assert_eq!(padded.parse::<Vec2d>(), Ok(direction.unit().scaled(magnitude)));
+    }
+}
@@ -0,0 +1,84 @@
+use std::num::ParseIntError;
+use std::time::Instant;
+
+pub use common::report::DayAnswers;
+
+pub fn calculate_requirement(mass: i32) -> i32 {
+    mass / 3 - 2
+}
+
+pub fn calculate_requirement_with_fuel(mass: i32) -> i32 {
+    let req_with_fuel = calculate_requirement(mass);
+    if req_with_fuel < 1 {
+        return 0;
+    }
+
+    req_with_fuel + calculate_requirement_with_fuel(req_with_fuel)
+}
+
+// Parses one module mass per line.
+pub fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Vec<i32>, ParseIntError> {
+    lines
+        .iter()
+        .map(|line| line.as_ref().trim().parse::<i32>())
+        .collect()
+}
+
+// Returns the total fuel requirement without and with the mass of the fuel.
+pub fn solve(masses: &[i32]) -> (i32, i32) {
+    let total_req = masses.iter().map(|mass| calculate_requirement(*mass)).sum();
+    let total_req_with_fuel = masses
+        .iter()
+        .map(|mass| calculate_requirement_with_fuel(*mass))
+        .sum();
+    (total_req, total_req_with_fuel)
+}
+
+// Same as solve, with the answers formatted for the runner.
+pub fn day_answers(masses: &[i32]) -> DayAnswers {
+    let start = Instant::now();
+    let (part1, part2) = solve(masses);
+    DayAnswers {
+        day: 1,
+        part1: part1.to_string(),
+        part2: part2.to_string(),
+        elapsed: start.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn part_one_examples_test() {
+        let test_cases = [(12, 2), (14, 2), (1969, 654), (100756, 33583)];
+
+        for expected in test_cases.iter() {
+            assert_eq!(calculate_requirement(expected.0), expected.1);
+        }
+    }
+
+    #[test]
+    fn part_two_examples_test() {
+        let test_cases = [(14, 2), (1969, 966), (100756, 50346)];
+
+        for expected in test_cases.iter() {
+            assert_eq!(calculate_requirement_with_fuel(expected.0), expected.1);
+        }
+    }
+
+    #[test]
+    fn solve_test() {
+        assert_eq!(solve(&[12, 14, 1969, 100756]), (34241, 51316));
+    }
+
+    #[test]
+    fn day_answers_test() {
+        let answers = day_answers(&[12, 14, 1969, 100756]);
+
+        assert_eq!(answers.day, 1);
+        assert_eq!(answers.part1, "34241");
+        assert_eq!(answers.part2, "51316");
+    }
+}
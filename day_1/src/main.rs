@@ -76,56 +76,17 @@
 //
 // Both parts of this puzzle are complete! They provide two gold stars: **
 
+use day_1::{parse, solve};
+
 use std::io;
 use std::io::prelude::*;
 
-fn calculate_requirement(mass: i32) -> i32 {
-    mass / 3 - 2
-}
-
-fn calculate_requirement_with_fuel(mass: i32) -> i32 {
-    let req_with_fuel = calculate_requirement(mass);
-    if req_with_fuel < 1 {
-        return 0;
-    }
-
-    req_with_fuel + calculate_requirement_with_fuel(req_with_fuel)
-}
-
 fn main() {
-    let mut total_req = 0;
-    let mut total_req_with_fuel = 0;
     let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let mass = line.unwrap().parse::<i32>().unwrap();
-        total_req += calculate_requirement(mass);
-        total_req_with_fuel += calculate_requirement_with_fuel(mass);
-    }
+    let lines: Vec<String> = stdin.lock().lines().map(|line| line.unwrap()).collect();
+    let (total_req, total_req_with_fuel) = solve(&parse(&lines).unwrap());
     println!(
         "Fuel requirements: {}\nFuel requirements including fuel mass: {}\n",
         total_req, total_req_with_fuel
     );
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn part_one_examples_test() {
-        let test_cases = vec![(12, 2), (14, 2), (1969, 654), (100756, 33583)];
-
-        for expected in test_cases.iter() {
-            assert_eq!(calculate_requirement(expected.0), expected.1);
-        }
-    }
-
-    #[test]
-    fn part_two_examples_test() {
-        let test_cases = vec![(14, 2), (1969, 966), (100756, 50346)];
-
-        for expected in test_cases.iter() {
-            assert_eq!(calculate_requirement_with_fuel(expected.0), expected.1);
-        }
-    }
-}
@@ -0,0 +1,186 @@
+use std::num::ParseIntError;
+use std::time::Instant;
+
+pub use common::report::DayAnswers;
+pub use common::AocError;
+
+pub const EXPECTED_OUTPUT_PART_TWO: usize = 19690720;
+pub const NOUN: usize = 1;
+pub const VERB: usize = 2;
+
+// Runs the program until it halts. An unknown opcode, an address past the end
+// of memory or a value too large for a usize stops it with an error.
+pub fn execute(program: &mut [usize]) -> Result<(), AocError> {
+    let read = |program: &[usize], address: usize| {
+        program
+            .get(address)
+            .copied()
+            .ok_or(AocError::AddressOutOfRange(address))
+    };
+
+    let mut ip = 0;
+    loop {
+        let opcode = read(program, ip)?;
+        let operation: fn(usize, usize) -> Option<usize> = match opcode {
+            1 => usize::checked_add,
+            2 => usize::checked_mul,
+            99 => return Ok(()),
+            _ => {
+                return Err(AocError::IllegalOpcode {
+                    opcode,
+                    address: ip,
+                })
+            }
+        };
+        let op_one = read(program, read(program, ip + 1)?)?;
+        let op_two = read(program, read(program, ip + 2)?)?;
+        let destination = read(program, ip + 3)?;
+        let value = operation(op_one, op_two).ok_or(AocError::Overflow("intcode value"))?;
+        *program
+            .get_mut(destination)
+            .ok_or(AocError::AddressOutOfRange(destination))? = value;
+        ip += 4;
+    }
+}
+
+// Runs a copy of the program with the noun and verb as its inputs, returning
+// its output at address 0.
+fn run(memory: &[usize], noun: usize, verb: usize) -> Result<usize, AocError> {
+    let mut program = memory.to_vec();
+    if program.len() <= VERB {
+        return Err(AocError::AddressOutOfRange(VERB));
+    }
+    program[NOUN] = noun;
+    program[VERB] = verb;
+    execute(&mut program)?;
+
+    Ok(program[0])
+}
+
+pub fn part_one(memory: &[usize]) -> Result<usize, AocError> {
+    run(memory, 12, 2)
+}
+
+pub fn part_two(memory: &[usize]) -> Result<Option<usize>, AocError> {
+    for noun in 0..100 {
+        for verb in 0..100 {
+            if run(memory, noun, verb)? == EXPECTED_OUTPUT_PART_TWO {
+                return Ok(Some(100 * noun + verb));
+            }
+        }
+    }
+    Ok(None)
+}
+
+// Parses a comma-separated Intcode program.
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseIntError> {
+    input
+        .trim()
+        .split(',')
+        .map(|c| c.parse::<usize>())
+        .collect()
+}
+
+pub fn solve(memory: &[usize]) -> Result<(usize, Option<usize>), AocError> {
+    Ok((part_one(memory)?, part_two(memory)?))
+}
+
+// Same as solve, with the answers formatted for the runner. Fails when running
+// the program fails or no noun and verb give the expected output.
+pub fn day_answers(memory: &[usize]) -> Result<DayAnswers, AocError> {
+    let start = Instant::now();
+    let (part1, part2) = solve(memory)?;
+    let part2 = part2.ok_or(AocError::NotFound("solution"))?;
+    Ok(DayAnswers {
+        day: 2,
+        part1: part1.to_string(),
+        part2: part2.to_string(),
+        elapsed: start.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn part_one_examples_test() {
+        let mut test_cases = [
+            (vec![1, 0, 0, 0, 99], vec![2, 0, 0, 0, 99]),
+            (vec![2, 3, 0, 3, 99], vec![2, 3, 0, 6, 99]),
+            (vec![2, 4, 4, 5, 99, 0], vec![2, 4, 4, 5, 99, 9801]),
+            (
+                vec![1, 1, 1, 4, 99, 5, 6, 0, 99],
+                vec![30, 1, 1, 4, 2, 5, 6, 0, 99],
+            ),
+        ];
+
+        for expected in test_cases.iter_mut() {
+            execute(&mut expected.0).unwrap();
+            assert_eq!(expected.0, expected.1);
+        }
+    }
+
+    #[test]
+    fn day_answers_test() {
+        let memory = parse(include_str!("../input.txt")).unwrap();
+        let answers = day_answers(&memory).unwrap();
+
+        assert_eq!(answers.day, 2);
+        assert_eq!(answers.part1, "5866714");
+        assert_eq!(answers.part2, "5208");
+
+        // A program that halts right away never produces the expected output.
+        assert!(matches!(
+            day_answers(&[99, 0, 0]),
+            Err(AocError::NotFound("solution"))
+        ));
+    }
+
+    #[test]
+    fn execute_error_test() {
+        assert!(matches!(
+            execute(&mut [1, 0, 0, 0, 42]),
+            Err(AocError::IllegalOpcode {
+                opcode: 42,
+                address: 4
+            })
+        ));
+        assert!(matches!(
+            execute(&mut [1, 0, 9, 0, 99]),
+            Err(AocError::AddressOutOfRange(9))
+        ));
+        assert!(matches!(
+            execute(&mut [1, 0, 0, 7, 99]),
+            Err(AocError::AddressOutOfRange(7))
+        ));
+        assert!(matches!(
+            execute(&mut [1, 0, 0]),
+            Err(AocError::AddressOutOfRange(3))
+        ));
+        assert!(matches!(
+            execute(&mut [2, 5, 5, 0, 99, usize::MAX]),
+            Err(AocError::Overflow(_))
+        ));
+        // Running off the end without halting is out of range as well.
+        assert!(matches!(
+            execute(&mut [1, 0, 0, 0]),
+            Err(AocError::AddressOutOfRange(4))
+        ));
+    }
+
+    #[test]
+    fn solve_error_test() {
+        assert!(matches!(
+            part_one(&[1, 0]),
+            Err(AocError::AddressOutOfRange(VERB))
+        ));
+        assert!(matches!(
+            solve(&[7, 0, 0]),
+            Err(AocError::IllegalOpcode {
+                opcode: 7,
+                address: 0
+            })
+        ));
+    }
+}
@@ -143,93 +143,29 @@
 //
 // Both parts of this puzzle are complete! They provide two gold stars: **
 
+use day_2::{parse, part_one, part_two, AocError};
+
 use std::io;
 use std::io::prelude::*;
+use std::process;
 
-const EXPECTED_OUTPUT_PART_TWO: usize = 19690720;
-const NOUN: usize = 1;
-const VERB: usize = 2;
-
-fn execute(program: &mut [usize]) {
-    let mut ip = 0;
-    loop {
-        match program[ip] {
-            1 => {
-                let op_one = program[program[ip + 1]];
-                let op_two = program[program[ip + 2]];
-                let destination = program[ip + 3];
-                program[destination] = op_one + op_two;
-                ip += 4;
-            }
-            2 => {
-                let op_one = program[program[ip + 1]];
-                let op_two = program[program[ip + 2]];
-                let destination = program[ip + 3];
-                program[destination] = op_one * op_two;
-                ip += 4;
-            }
-            99 => return,
-            _ => panic!("Illegal opcode: {}", program[ip]),
-        }
-    }
-}
-
-fn part_one(memory: &[usize]) -> Option<usize> {
-    let mut part_one_program = memory.to_vec();
-    part_one_program[NOUN] = 12;
-    part_one_program[VERB] = 2;
-    execute(&mut part_one_program);
-
-    Some(part_one_program[0])
-}
-
-fn part_two(memory: &[usize]) -> Option<usize> {
-    for noun in 0..100 {
-        for verb in 0..100 {
-            let mut part_two_program = memory.to_vec();
-            part_two_program[NOUN] = noun;
-            part_two_program[VERB] = verb;
-            execute(&mut part_two_program);
-            if part_two_program[0] == EXPECTED_OUTPUT_PART_TWO {
-                return Some(100 * noun + verb);
-            }
-        }
+fn run(memory: &[usize]) -> Result<(), AocError> {
+    println!("Part 1 answer: {}", part_one(memory)?);
+    match part_two(memory)? {
+        Some(answer) => println!("Part 2 answer: {}", answer),
+        None => return Err(AocError::NotFound("solution")),
     }
-    None
+    Ok(())
 }
 
 fn main() {
     let mut buf = String::new();
     io::stdin().lock().read_to_string(&mut buf).unwrap();
 
-    let memory: Vec<usize> = buf
-        .split(',')
-        .map(|c| c.parse::<usize>().unwrap())
-        .collect();
-
-    println!("Part 1 answer: {}", part_one(&memory).unwrap());
-    println!("Part 2 answer: {}", part_two(&memory).unwrap());
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn part_one_examples_test() {
-        let mut test_cases = [
-            (vec![1, 0, 0, 0, 99], vec![2, 0, 0, 0, 99]),
-            (vec![2, 3, 0, 3, 99], vec![2, 3, 0, 6, 99]),
-            (vec![2, 4, 4, 5, 99, 0], vec![2, 4, 4, 5, 99, 9801]),
-            (
-                vec![1, 1, 1, 4, 99, 5, 6, 0, 99],
-                vec![30, 1, 1, 4, 2, 5, 6, 0, 99],
-            ),
-        ];
+    let memory = parse(&buf).unwrap();
 
-        for expected in test_cases.iter_mut() {
-            execute(&mut expected.0);
-            assert_eq!(expected.0, expected.1);
-        }
+    if let Err(e) = run(&memory) {
+        eprintln!("{}", e);
+        process::exit(1);
     }
 }
@@ -0,0 +1,23 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day_3::{fewest_combined_steps, get_points, parse, solve};
+
+fn fewest_combined_steps_benchmark(c: &mut Criterion) {
+    let a = get_points(&parse("R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51").unwrap());
+    let b = get_points(&parse("U98,R91,D20,R16,D67,R40,U7,R15,U6,R7").unwrap());
+
+    c.bench_function("fewest_combined_steps", |bencher| {
+        bencher.iter(|| fewest_combined_steps(black_box(&a), black_box(&b)))
+    });
+}
+
+fn solve_benchmark(c: &mut Criterion) {
+    let a = get_points(&parse("R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51").unwrap());
+    let b = get_points(&parse("U98,R91,D20,R16,D67,R40,U7,R15,U6,R7").unwrap());
+
+    c.bench_function("solve", |bencher| {
+        bencher.iter(|| solve(black_box(&a), black_box(&b)))
+    });
+}
+
+criterion_group!(benches, fewest_combined_steps_benchmark, solve_benchmark);
+criterion_main!(benches);
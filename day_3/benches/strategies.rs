@@ -0,0 +1,64 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use day_3::{closest_intersection_distance_by_segments, fewest_combined_steps_by_segments};
+use day_3::{get_points, segments, solve, Direction, Vec2d};
+
+// Builds a wire of the given number of segments from a linear congruential
+// generator, so that every run benchmarks the same input. Turns are always a
+// quarter turn, which keeps the wire from doubling back on itself.
+fn synthetic_wire(segment_count: usize, max_length: u32, seed: u32) -> Vec<Vec2d> {
+    let mut state = seed;
+    let mut next = move || {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        state >> 16
+    };
+    let mut direction = Direction::Right;
+    (0..segment_count)
+        .map(|_| {
+            direction = match (direction, next() % 2) {
+                (Direction::Up, 0) | (Direction::Down, 1) => Direction::Left,
+                (Direction::Up, _) | (Direction::Down, _) => Direction::Right,
+                (Direction::Left, 0) | (Direction::Right, 1) => Direction::Down,
+                (Direction::Left, _) | (Direction::Right, _) => Direction::Up,
+            };
+            direction.unit().scaled((next() % max_length + 1) as i32)
+        })
+        .collect()
+}
+
+fn strategies_benchmark(c: &mut Criterion) {
+    let sizes = [("small", 10, 20), ("medium", 100, 100), ("large", 300, 500)];
+    let mut group = c.benchmark_group("intersection strategies");
+
+    for (name, segment_count, max_length) in sizes.iter() {
+        let a = synthetic_wire(*segment_count, *max_length, 1);
+        let b = synthetic_wire(*segment_count, *max_length, 2);
+        let (points_a, points_b) = (get_points(&a), get_points(&b));
+        let (segments_a, segments_b) = (segments(&a), segments(&b));
+
+        let by_segments = |a, b| {
+            Some((
+                closest_intersection_distance_by_segments(a, b)?,
+                fewest_combined_steps_by_segments(a, b).unwrap()?,
+            ))
+        };
+        let answers = solve(&points_a, &points_b).unwrap();
+        assert!(answers.is_some(), "the {} wires do not cross", name);
+        assert_eq!(
+            answers,
+            by_segments(&segments_a, &segments_b),
+            "strategies disagree on the {} input",
+            name
+        );
+
+        group.bench_with_input(BenchmarkId::new("points", name), name, |bencher, _| {
+            bencher.iter(|| solve(black_box(&points_a), black_box(&points_b)))
+        });
+        group.bench_with_input(BenchmarkId::new("segments", name), name, |bencher, _| {
+            bencher.iter(|| by_segments(black_box(&segments_a), black_box(&segments_b)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, strategies_benchmark);
+criterion_main!(benches);
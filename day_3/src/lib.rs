@@ -0,0 +1,2222 @@
+use std::cell::OnceCell;
+use std::cmp::{max, min, Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::iter;
+use std::str::FromStr;
+use std::time::Instant;
+
+mod render;
+
+pub use common::report::DayAnswers;
+pub use common::{parse_step, AocError, Direction, ParseSegmentError, Vec2d, WideVec2d};
+
+use common::ByDistance;
+pub use render::{render_grid, write_crossings_csv, write_ppm, write_svg};
+
+// Parses a path like "R8,U5,L5,D3". Spaces around the segments are ignored.
+pub fn parse(path: &str) -> Result<Vec<Vec2d>, ParseSegmentError> {
+    path.split(',').map(|p| p.trim().parse::<Vec2d>()).collect()
+}
+
+// Parses one wire per line. Surrounding whitespace, such as the carriage return
+// left behind by CRLF line endings, is ignored. Blank lines and comment lines
+// starting with '#' are skipped. Lines may name their wire, see
+// parse_labeled_wires; the names are dropped.
+pub fn parse_wires<S: AsRef<str>>(lines: &[S]) -> Result<Vec<Vec<Vec2d>>, AocError> {
+    let wires = parse_labeled_wires(lines)?;
+    Ok(wires.into_iter().map(|wire| wire.vertices).collect())
+}
+
+// A wire together with the name it is reported by.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LabeledWire {
+    pub name: String,
+    pub vertices: Vec<Vec2d>,
+}
+
+// Same as parse_wires, but keeps the names given to the wires like
+// "A: R8,U5,L5,D3". Wires without a name are called wire0, wire1 and so on
+// after their position among the wires.
+pub fn parse_labeled_wires<S: AsRef<str>>(lines: &[S]) -> Result<Vec<LabeledWire>, AocError> {
+    let wires = wire_lines(lines)
+        .enumerate()
+        .map(|(i, (_, line))| parse_labeled_wire(line, i))
+        .collect::<Result<_, _>>()?;
+    Ok(wires)
+}
+
+// Checks that every wire parses without going on to trace it. Returns the
+// number of segments of each wire, or the first error together with the line
+// it was found on.
+pub fn check_wires<S: AsRef<str>>(lines: &[S]) -> Result<Vec<usize>, AocError> {
+    wire_lines(lines)
+        .enumerate()
+        .map(|(i, (number, line))| match parse_labeled_wire(line, i) {
+            Ok(wire) => Ok(wire.vertices.len()),
+            Err(e) => Err(AocError::from(e).at_line(number)),
+        })
+        .collect()
+}
+
+// Returns the pairs of indices of wires with the same segments, each later wire
+// paired with the first one it repeats. Two identical wires cross at every
+// point they enter, which almost always means a line was pasted twice.
+pub fn duplicate_wires(wires: &[Vec<Vec2d>]) -> Vec<(usize, usize)> {
+    (1..wires.len())
+        .filter_map(|j| {
+            wires[..j]
+                .iter()
+                .position(|wire| *wire == wires[j])
+                .map(|i| (i, j))
+        })
+        .collect()
+}
+
+// The lines holding a wire, trimmed and paired with their 1-based line number.
+fn wire_lines<S: AsRef<str>>(lines: &[S]) -> impl Iterator<Item = (usize, &str)> {
+    lines
+        .iter()
+        .map(|line| line.as_ref().trim())
+        .zip(1..)
+        .filter(|(line, _)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line, number)| (number, line))
+}
+
+// Parses a wire with an optional name, naming it after its index otherwise.
+fn parse_labeled_wire(line: &str, index: usize) -> Result<LabeledWire, ParseSegmentError> {
+    let (name, path) = match line.find(':') {
+        Some(colon) if !line[..colon].trim().is_empty() => {
+            (line[..colon].trim().to_string(), &line[colon + 1..])
+        }
+        Some(colon) => (format!("wire{}", index), &line[colon + 1..]),
+        None => (format!("wire{}", index), line),
+    };
+    let vertices = parse(path)?;
+    Ok(LabeledWire { name, vertices })
+}
+
+// Same as all_pairwise_intersections, but names the two wires meeting at each
+// point.
+pub fn labeled_pairwise_intersections(wires: &[LabeledWire]) -> Vec<(Vec2d, &str, &str)> {
+    let points: Vec<Vec<Vec2d>> = wires
+        .iter()
+        .map(|wire| get_points(&wire.vertices))
+        .collect();
+    all_pairwise_intersections(&points)
+        .into_iter()
+        .map(|(point, i, j)| (point, wires[i].name.as_str(), wires[j].name.as_str()))
+        .collect()
+}
+
+// Walks the steps, each a unit vector and a length, from start one grid square
+// at a time, yielding each point entered.
+fn walk<I>(steps: I, start: Vec2d) -> impl Iterator<Item = Vec2d>
+where
+    I: Iterator<Item = (Vec2d, i32)>,
+{
+    steps
+        .flat_map(|(unit, line_length)| iter::repeat_n(unit, line_length as usize))
+        .scan(start, |pos, unit| {
+            *pos += unit;
+            Some(*pos)
+        })
+}
+
+// Walks the wire one grid square at a time, yielding each point entered
+// together with the number of steps taken to reach it.
+pub fn trace(vertices: &[Vec2d]) -> impl Iterator<Item = (u64, Vec2d)> + '_ {
+    trace_from(vertices, Vec2d::ORIGIN)
+}
+
+// Same as trace, but for a wire leaving a central port at start.
+pub fn trace_from(vertices: &[Vec2d], start: Vec2d) -> impl Iterator<Item = (u64, Vec2d)> + '_ {
+    walk(
+        vertices.iter().filter_map(|vertex| axis_step(*vertex)),
+        start,
+    )
+    .zip(1..)
+    .map(|(point, step)| (step, point))
+}
+
+// Splits a segment into the unit vector it runs along and its length, skipping
+// empty segments. Wires only ever run along the grid, so a diagonal segment is
+// a bug in the caller and panics rather than tracing a path the wire never took.
+//
+// The invariant is checked here rather than carried by a type such as an
+// AxisSegment newtype: segments are plain Vec2d values throughout the public
+// API, from parse to solve, and Vec2d is shared with the other days through
+// common, so a newtype would change nearly every signature in this crate.
+// Tracing and segments both go through here instead.
+fn axis_step(vertex: Vec2d) -> Option<(Vec2d, i32)> {
+    if vertex == Vec2d::ORIGIN {
+        return None;
+    }
+    match vertex.as_step() {
+        Some((direction, length)) => Some((direction.unit(), length)),
+        None => panic!("segment {:?} is not axis-aligned", vertex),
+    }
+}
+
+// A wire path such as "R8,U5,L5,D3", kept as the list of segments it was
+// parsed from so that it can be written back out unchanged. The points it
+// enters are only collected once they are asked for.
+#[derive(Clone, Debug)]
+pub struct Wire {
+    segments: Vec<(Direction, i32)>,
+    visited: OnceCell<HashSet<Vec2d>>,
+}
+
+impl Wire {
+    pub fn vertices(&self) -> Vec<Vec2d> {
+        self.segments
+            .iter()
+            .map(|(direction, magnitude)| direction.unit().scaled(*magnitude))
+            .collect()
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Vec2d> + '_ {
+        walk(
+            self.segments
+                .iter()
+                .map(|(direction, magnitude)| (direction.unit(), *magnitude)),
+            Vec2d::ORIGIN,
+        )
+    }
+
+    // Returns whether the wire enters the point. The central port never counts,
+    // even for a wire coming back through it.
+    pub fn passes_through(&self, p: Vec2d) -> bool {
+        p != Vec2d::ORIGIN
+            && self
+                .visited
+                .get_or_init(|| self.points().collect())
+                .contains(&p)
+    }
+}
+
+// Wires are equal when they have the same segments, whether or not their
+// points have been collected yet.
+impl PartialEq for Wire {
+    fn eq(&self, other: &Wire) -> bool {
+        self.segments == other.segments
+    }
+}
+
+impl Eq for Wire {}
+
+impl FromStr for Wire {
+    type Err = ParseSegmentError;
+
+    fn from_str(s: &str) -> Result<Wire, ParseSegmentError> {
+        let segments = s
+            .split(',')
+            .map(|p| parse_step(p.trim()))
+            .collect::<Result<_, _>>()?;
+        Ok(Wire {
+            segments,
+            visited: OnceCell::new(),
+        })
+    }
+}
+
+impl fmt::Display for Wire {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (direction, magnitude)) in self.segments.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}{}", direction, magnitude)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn get_points(vertices: &[Vec2d]) -> Vec<Vec2d> {
+    trace(vertices).map(|(_, point)| point).collect()
+}
+
+pub fn get_points_from(vertices: &[Vec2d], start: Vec2d) -> Vec<Vec2d> {
+    trace_from(vertices, start)
+        .map(|(_, point)| point)
+        .collect()
+}
+
+// Returns where the wire ends, found by adding up its segments without tracing
+// the points in between. The sum is wide, as a wire whose segments each fit in
+// an i32 can still end past i32::MAX.
+pub fn endpoint(vertices: &[Vec2d]) -> WideVec2d {
+    vertices.iter().fold(WideVec2d::ORIGIN, |end, vertex| {
+        end + WideVec2d::from(*vertex)
+    })
+}
+
+// Merges consecutive segments running the same way, so that "R5,R5,U3" becomes
+// "R10,U3", and drops empty ones. The wire enters the same points in the same
+// order, so the steps to reach them do not change either.
+pub fn normalize(vertices: &[Vec2d]) -> Vec<Vec2d> {
+    let mut normalized: Vec<Vec2d> = Vec::with_capacity(vertices.len());
+    for vertex in vertices.iter().filter(|vertex| **vertex != Vec2d::ORIGIN) {
+        match normalized.last_mut() {
+            Some(last) if last.signum() == vertex.signum() => *last += *vertex,
+            _ => normalized.push(*vertex),
+        }
+    }
+    normalized
+}
+
+// Returns the number of grid squares the wire enters, which like the endpoint
+// may be past i32::MAX.
+pub fn total_length(vertices: &[Vec2d]) -> i64 {
+    vertices
+        .iter()
+        .map(|vertex| i64::from(vertex.manhattan_distance()))
+        .sum()
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TurnKind {
+    Left,
+    Right,
+}
+
+// Classifies every change of direction between consecutive segments, with +y
+// pointing up. Segments running the same or the opposite way are not turns.
+pub fn turns(vertices: &[Vec2d]) -> Vec<TurnKind> {
+    vertices
+        .windows(2)
+        .filter_map(|pair| match pair[0].cross(pair[1]).signum() {
+            1 => Some(TurnKind::Left),
+            -1 => Some(TurnKind::Right),
+            _ => None,
+        })
+        .collect()
+}
+
+// Counts the changes of direction along the wire.
+pub fn turn_count(vertices: &[Vec2d]) -> usize {
+    turns(vertices).len()
+}
+
+// Returns the lower left and upper right corners of the smallest box that holds
+// both the points and the central port.
+pub fn bounding_box(points: &[Vec2d]) -> (Vec2d, Vec2d) {
+    let origin = Vec2d::ORIGIN;
+    points.iter().fold((origin, origin), |(low, high), point| {
+        (
+            Vec2d {
+                x: min(low.x, point.x),
+                y: min(low.y, point.y),
+            },
+            Vec2d {
+                x: max(high.x, point.x),
+                y: max(high.y, point.y),
+            },
+        )
+    })
+}
+
+// Returns the number of columns and rows covered by the bounding box.
+pub fn span(points: &[Vec2d]) -> (i32, i32) {
+    let (low, high) = bounding_box(points);
+    (high.x - low.x + 1, high.y - low.y + 1)
+}
+
+// Keeps the steps of the first visit to each point, later visits never making
+// the signal arrive any sooner.
+fn first_visits<P, I>(visits: I) -> HashMap<P, u64>
+where
+    P: Eq + Hash,
+    I: Iterator<Item = (u64, P)>,
+{
+    let mut steps = HashMap::new();
+    for (step, point) in visits {
+        steps.entry(point).or_insert(step);
+    }
+    steps
+}
+
+// Maps each point on the wire to the number of steps taken the first time the
+// wire reaches it.
+pub fn first_visit_steps(wire: &[Vec2d]) -> HashMap<&Vec2d, u64> {
+    first_visits((1..).zip(wire.iter()))
+}
+
+// Same as first_visit_steps, but traced straight from the vertices without
+// holding on to the points, e.g. to draw how long the signal takes to reach
+// each square.
+pub fn step_field(vertices: &[Vec2d]) -> HashMap<Vec2d, u64> {
+    first_visits(trace(vertices))
+}
+
+// Returns every point the wire occupies more than once, except for the central
+// port, in the order the wire first comes back to them.
+pub fn self_intersections(wire: &[Vec2d]) -> Vec<Vec2d> {
+    let first_visits = first_visit_steps(wire);
+    let mut reported = HashSet::new();
+    let mut crossings = Vec::new();
+
+    for (step, point) in (1..).zip(wire.iter()) {
+        if *point != Vec2d::ORIGIN && first_visits[point] != step && reported.insert(point) {
+            crossings.push(*point);
+        }
+    }
+    crossings
+}
+
+// Returns the length of the shortest path between two points moving one grid
+// square at a time up, down, left or right without entering a blocked point,
+// or None when there is no such path. The search is confined to the bounding
+// box of the blocked points and both ends grown by one square: that border is
+// free, so no shortest path ever needs to go further out.
+pub fn shortest_path_avoiding(blocked: &HashSet<Vec2d>, from: Vec2d, to: Vec2d) -> Option<usize> {
+    if blocked.contains(&from) || blocked.contains(&to) {
+        return None;
+    }
+    let corners: Vec<Vec2d> = blocked.iter().copied().chain(vec![from, to]).collect();
+    let (low, high) = bounding_box(&corners);
+    let inside = |point: &Vec2d| {
+        (low.x - 1..=high.x + 1).contains(&point.x) && (low.y - 1..=high.y + 1).contains(&point.y)
+    };
+
+    let mut seen: HashSet<Vec2d> = iter::once(from).collect();
+    let mut queue: VecDeque<(Vec2d, usize)> = iter::once((from, 0)).collect();
+    while let Some((point, distance)) = queue.pop_front() {
+        if point == to {
+            return Some(distance);
+        }
+        for neighbor in point.neighbors4().iter() {
+            if inside(neighbor) && !blocked.contains(neighbor) && seen.insert(*neighbor) {
+                queue.push_back((*neighbor, distance + 1));
+            }
+        }
+    }
+    None
+}
+
+// Orders points by distance from the central port, then by position, so that
+// they always come out in the same order.
+fn distance_order(point: &Vec2d) -> (i32, i32, i32) {
+    (point.manhattan_distance(), point.x, point.y)
+}
+
+// Returns every point where two distinct wires cross together with the indices
+// of the two wires. Points where a wire crosses itself are not included, nor is
+// the central port, even when wires come back through it. The crossings of each
+// pair of wires are ordered closest to the origin first.
+pub fn all_pairwise_intersections(wires: &[Vec<Vec2d>]) -> Vec<(Vec2d, usize, usize)> {
+    let points: Vec<HashSet<&Vec2d>> = wires
+        .iter()
+        .map(|wire| {
+            wire.iter()
+                .filter(|point| **point != Vec2d::ORIGIN)
+                .collect()
+        })
+        .collect();
+    let mut intersections = Vec::new();
+
+    for i in 0..points.len() {
+        for j in i + 1..points.len() {
+            let mut shared: Vec<Vec2d> = points[i].intersection(&points[j]).map(|p| **p).collect();
+            shared.sort_by_key(distance_order);
+            intersections.extend(shared.into_iter().map(|point| (point, i, j)));
+        }
+    }
+    intersections
+}
+
+// Returns the number of distinct points entered by any of the wires, given by
+// their points. The central port is not counted, even when a wire comes back
+// through it.
+pub fn covered_cell_count(wires: &[Vec<Vec2d>]) -> usize {
+    wires
+        .iter()
+        .flatten()
+        .filter(|point| **point != Vec2d::ORIGIN)
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+// Returns the point closest to the central port where at least two distinct
+// wires meet, together with the indices of every wire passing through it. Ties
+// are broken on x and then y. The central port itself does not count.
+pub fn closest_multi_wire_crossing(wires: &[Vec<Vec2d>]) -> Option<(Vec2d, Vec<usize>)> {
+    let mut visitors: HashMap<Vec2d, Vec<usize>> = HashMap::new();
+    for (id, wire) in wires.iter().enumerate() {
+        for point in wire.iter().filter(|point| **point != Vec2d::ORIGIN) {
+            let ids = visitors.entry(*point).or_default();
+            if ids.last() != Some(&id) {
+                ids.push(id);
+            }
+        }
+    }
+    visitors
+        .into_iter()
+        .filter(|(_, ids)| ids.len() >= 2)
+        .min_by_key(|(point, _)| distance_order(point))
+}
+
+pub fn closest_pairwise_distance(intersections: &[(Vec2d, usize, usize)]) -> Option<i32> {
+    intersections
+        .iter()
+        .map(|(point, _, _)| point.manhattan_distance())
+        .min()
+}
+
+// Returns the fewest combined steps to reach one of the intersections, or an
+// error if adding up the steps of two wires overflows.
+pub fn fewest_pairwise_steps(
+    wires: &[Vec<Vec2d>],
+    intersections: &[(Vec2d, usize, usize)],
+) -> Result<Option<u64>, AocError> {
+    let first_visits: Vec<HashMap<&Vec2d, u64>> =
+        wires.iter().map(|wire| first_visit_steps(wire)).collect();
+
+    let mut fewest = None;
+    for (point, i, j) in intersections.iter() {
+        let steps = combined_steps(first_visits[*i][point], first_visits[*j][point])?;
+        fewest = Some(fewest.map_or(steps, |fewest| min(fewest, steps)));
+    }
+    Ok(fewest)
+}
+
+// Adds up the steps two wires take to reach a crossing. Steps are counted in
+// u64 so that they do not depend on the width of usize, and the sum is checked
+// rather than allowed to wrap around.
+pub fn combined_steps(steps_a: u64, steps_b: u64) -> Result<u64, AocError> {
+    steps_a
+        .checked_add(steps_b)
+        .ok_or(AocError::Overflow("combined steps"))
+}
+
+// Same as all_pairwise_intersections, but fails when there are too few wires
+// to cross.
+fn checked_pairwise_intersections(
+    wires: &[Vec<Vec2d>],
+) -> Result<Vec<(Vec2d, usize, usize)>, AocError> {
+    if wires.len() < 2 {
+        return Err(AocError::InsufficientInput {
+            what: "wires",
+            expected: 2,
+            found: wires.len(),
+        });
+    }
+    Ok(all_pairwise_intersections(wires))
+}
+
+// Returns the distance to the closest intersection between any pair of wires.
+pub fn part_one(wires: &[Vec<Vec2d>]) -> Result<i32, AocError> {
+    let intersections = checked_pairwise_intersections(wires)?;
+    closest_pairwise_distance(&intersections).ok_or(AocError::NotFound("intersections"))
+}
+
+// Returns the fewest combined steps to reach an intersection between any pair
+// of wires.
+pub fn part_two(wires: &[Vec<Vec2d>]) -> Result<u64, AocError> {
+    let intersections = checked_pairwise_intersections(wires)?;
+    fewest_pairwise_steps(wires, &intersections)?.ok_or(AocError::NotFound("intersections"))
+}
+
+// Returns the answers to both parts, finding the intersections only once.
+pub fn solve_pairwise(wires: &[Vec<Vec2d>]) -> Result<(i32, u64), AocError> {
+    let intersections = checked_pairwise_intersections(wires)?;
+    match (
+        closest_pairwise_distance(&intersections),
+        fewest_pairwise_steps(wires, &intersections)?,
+    ) {
+        (Some(distance), Some(steps)) => Ok((distance, steps)),
+        _ => Err(AocError::NotFound("intersections")),
+    }
+}
+
+// Same as solve_pairwise, with the answers formatted for the runner.
+pub fn day_answers(wires: &[Vec<Vec2d>]) -> Result<DayAnswers, AocError> {
+    let start = Instant::now();
+    let (distance, steps) = solve_pairwise(wires)?;
+    Ok(DayAnswers {
+        day: 3,
+        part1: distance.to_string(),
+        part2: steps.to_string(),
+        elapsed: start.elapsed(),
+    })
+}
+
+// The points both wires enter. The central port is never a crossing, even for
+// wires coming back through it, so every function built on this leaves it out.
+fn intersections<'a>(a: &'a [Vec2d], b: &[Vec2d]) -> HashSet<&'a Vec2d> {
+    let b: HashSet<&Vec2d> = b.iter().collect();
+    a.iter()
+        .filter(|point| **point != Vec2d::ORIGIN && b.contains(point))
+        .collect()
+}
+
+// Returns every intersection of two wires, closest to the origin first and
+// ties broken on x and then y.
+pub fn sorted_intersections(a: &[Vec2d], b: &[Vec2d]) -> Vec<Vec2d> {
+    let mut points: Vec<Vec2d> = intersections(a, b).into_iter().copied().collect();
+    points.sort_by_key(distance_order);
+    points
+}
+
+// How far the intersections of two wires are from the central port.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DistanceStats {
+    pub min: i32,
+    // The mean of the two middle distances when there is an even number of
+    // intersections.
+    pub median: f64,
+    pub max: i32,
+    pub count: usize,
+}
+
+// Summarizes the distances to the intersections of two wires, or gives None
+// when the wires do not cross.
+pub fn distance_stats(a: &[Vec2d], b: &[Vec2d]) -> Option<DistanceStats> {
+    let distances: Vec<i32> = sorted_intersections(a, b)
+        .iter()
+        .map(|point| point.manhattan_distance())
+        .collect();
+    let count = distances.len();
+    let median = match count {
+        0 => return None,
+        n if n % 2 == 1 => f64::from(distances[n / 2]),
+        n => f64::from(distances[n / 2 - 1] + distances[n / 2]) / 2.0,
+    };
+    Some(DistanceStats {
+        min: distances[0],
+        median,
+        max: distances[count - 1],
+        count,
+    })
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WireId {
+    A,
+    B,
+}
+
+// An intersection of two wires and the steps each wire needs to reach it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Crossing {
+    pub point: Vec2d,
+    pub steps_a: u64,
+    pub steps_b: u64,
+}
+
+impl Crossing {
+    // The steps both wires take to reach the crossing, or an error if adding
+    // them up overflows.
+    pub fn combined_steps(&self) -> Result<u64, AocError> {
+        combined_steps(self.steps_a, self.steps_b)
+    }
+
+    // The wire that reaches the crossing in fewer steps, or None on a tie.
+    pub fn faster_wire(&self) -> Option<WireId> {
+        match self.steps_a.cmp(&self.steps_b) {
+            Ordering::Less => Some(WireId::A),
+            Ordering::Greater => Some(WireId::B),
+            Ordering::Equal => None,
+        }
+    }
+}
+
+// Returns every intersection of two wires, closest to the origin first.
+pub fn crossings(a: &[Vec2d], b: &[Vec2d]) -> Vec<Crossing> {
+    let steps_a = first_visit_steps(a);
+    let steps_b = first_visit_steps(b);
+    let mut crossings: Vec<Crossing> = intersections(a, b)
+        .into_iter()
+        .map(|point| Crossing {
+            point: *point,
+            steps_a: steps_a[point],
+            steps_b: steps_b[point],
+        })
+        .collect();
+    crossings.sort_by_key(|crossing| distance_order(&crossing.point));
+    crossings
+}
+
+// The Manhattan distance from the central port to the closest point in the box
+// with the given corners.
+fn closest_distance(low: Vec2d, high: Vec2d) -> i32 {
+    Vec2d::new(0.clamp(low.x, high.x), 0.clamp(low.y, high.y)).manhattan_distance()
+}
+
+// Same as crossings for two wires given by their segments, but yielding the
+// crossings one at a time, so a caller looking for the first one within some
+// distance can stop early. Pairs of segments are visited closest to the central
+// port first and only as far out as needed: a crossing is yielded once every
+// pair that could hold a point at its distance has been looked at, which also
+// means its steps are those of the first visits.
+pub fn crossings_iter<'a>(
+    a: &'a [Segment],
+    b: &'a [Segment],
+) -> impl Iterator<Item = Crossing> + 'a {
+    let mut pairs: BinaryHeap<Reverse<(i32, usize, usize)>> = BinaryHeap::new();
+    for (i, segment_a) in a.iter().enumerate() {
+        for (j, segment_b) in b.iter().enumerate() {
+            if let Some((low, high)) = segment_a.shared_bounds(segment_b) {
+                pairs.push(Reverse((closest_distance(low, high), i, j)));
+            }
+        }
+    }
+    let mut found: HashMap<Vec2d, (u64, u64)> = HashMap::new();
+    let mut pending: BinaryHeap<Reverse<ByDistance>> = BinaryHeap::new();
+
+    iter::from_fn(move || loop {
+        let next_pair = pairs.peek().map(|Reverse((distance, _, _))| *distance);
+        if let Some(Reverse(ByDistance(point))) = pending.peek().copied() {
+            if next_pair.is_none_or(|distance| point.manhattan_distance() < distance) {
+                pending.pop();
+                let (steps_a, steps_b) = found[&point];
+                return Some(Crossing {
+                    point,
+                    steps_a,
+                    steps_b,
+                });
+            }
+        }
+
+        let Reverse((_, i, j)) = pairs.pop()?;
+        for point in a[i].overlap(&b[j]) {
+            if point == Vec2d::ORIGIN {
+                continue;
+            }
+            let steps = (a[i].steps_to(point), b[j].steps_to(point));
+            match found.get_mut(&point) {
+                Some(known) => *known = (min(known.0, steps.0), min(known.1, steps.1)),
+                None => {
+                    found.insert(point, steps);
+                    pending.push(Reverse(ByDistance(point)));
+                }
+            }
+        }
+    })
+}
+
+// Returns the intersections of two wires, given by their segments, no further
+// than radius from the central port, closest first. Pairs of segments further
+// out than radius are never looked at.
+pub fn crossings_within(a: &[Segment], b: &[Segment], radius: i32) -> Vec<Crossing> {
+    crossings_iter(a, b)
+        .take_while(|crossing| crossing.point.manhattan_distance() <= radius)
+        .collect()
+}
+
+// Counts the intersections of two wires in each quadrant around the central
+// port, in the order +x+y, -x+y, -x-y, +x-y. A point on an axis belongs to the
+// quadrant counterclockwise from it, so the positive x axis counts towards
+// +x+y, the positive y axis towards -x+y and so on.
+pub fn crossings_by_quadrant(a: &[Vec2d], b: &[Vec2d]) -> [usize; 4] {
+    let mut counts = [0; 4];
+    for point in intersections(a, b) {
+        let quadrant = match (point.x, point.y) {
+            (x, y) if x > 0 && y >= 0 => 0,
+            (x, y) if x <= 0 && y > 0 => 1,
+            (x, y) if x < 0 && y <= 0 => 2,
+            _ => 3,
+        };
+        counts[quadrant] += 1;
+    }
+    counts
+}
+
+// How two wires meet at a point they share.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum IntersectionKind {
+    // Both wires go straight through, one across the other.
+    Crossing,
+    // One wire ends or turns at the point without crossing the other, as in a
+    // T-junction.
+    Touch,
+    // The wires run along each other into or out of the point.
+    Overlap,
+}
+
+// The unit vectors pointing from point to the squares the wire comes from or
+// goes to around every visit to point.
+fn arms(wire: &[Vec2d], point: Vec2d) -> HashSet<Vec2d> {
+    let mut arms = HashSet::new();
+    for (i, _) in wire.iter().enumerate().filter(|(_, p)| **p == point) {
+        let previous = if i == 0 { Vec2d::ORIGIN } else { wire[i - 1] };
+        arms.insert(previous - point);
+        if let Some(next) = wire.get(i + 1) {
+            arms.insert(*next - point);
+        }
+    }
+    arms
+}
+
+// Tells how two wires meet at point from the way each of them enters and leaves
+// it. Gives None when point is the central port or is not on both wires.
+pub fn classify_intersection(a: &[Vec2d], b: &[Vec2d], point: Vec2d) -> Option<IntersectionKind> {
+    let (arms_a, arms_b) = (arms(a, point), arms(b, point));
+    let straight = |arms: &HashSet<Vec2d>| {
+        arms.len() == 2 && arms.iter().fold(Vec2d::ORIGIN, |sum, arm| sum + *arm) == Vec2d::ORIGIN
+    };
+
+    if point == Vec2d::ORIGIN || arms_a.is_empty() || arms_b.is_empty() {
+        None
+    } else if !arms_a.is_disjoint(&arms_b) {
+        Some(IntersectionKind::Overlap)
+    } else if straight(&arms_a) && straight(&arms_b) {
+        Some(IntersectionKind::Crossing)
+    } else {
+        Some(IntersectionKind::Touch)
+    }
+}
+
+pub fn closest_intersection_distance(a: &[Vec2d], b: &[Vec2d]) -> Option<i32> {
+    crossings(a, b)
+        .first()
+        .map(|crossing| crossing.point.manhattan_distance())
+}
+
+// Returns the intersection closest to the central port, ignoring the points in
+// excluded.
+pub fn closest_intersection_excluding(
+    a: &[Vec2d],
+    b: &[Vec2d],
+    excluded: &HashSet<Vec2d>,
+) -> Option<Vec2d> {
+    crossings(a, b)
+        .into_iter()
+        .map(|crossing| crossing.point)
+        .find(|point| !excluded.contains(point))
+}
+
+// Returns the intersection closest to the central port.
+pub fn closest_intersection(a: &[Vec2d], b: &[Vec2d]) -> Option<Vec2d> {
+    crossings(a, b).first().map(|crossing| crossing.point)
+}
+
+// Like closest_intersection_distance, but measures straight-line distance.
+pub fn closest_intersection_euclidean(a: &[Vec2d], b: &[Vec2d]) -> Option<f64> {
+    crossings(a, b)
+        .iter()
+        .map(|crossing| crossing.point.euclidean_distance())
+        .min_by(|x, y| x.partial_cmp(y).unwrap())
+}
+
+// Returns the crossing reached in the fewest combined steps. Of several equally
+// good crossings the one closest to the central port is picked.
+pub fn best_steps_crossing(a: &[Vec2d], b: &[Vec2d]) -> Result<Option<Crossing>, AocError> {
+    let mut best: Option<(u64, Crossing)> = None;
+    for crossing in crossings(a, b) {
+        let steps = crossing.combined_steps()?;
+        if best.is_none_or(|(fewest, _)| steps < fewest) {
+            best = Some((steps, crossing));
+        }
+    }
+    Ok(best.map(|(_, crossing)| crossing))
+}
+
+// Returns the fewest combined steps to reach an intersection, or an error if
+// adding up the steps of the two wires overflows.
+pub fn fewest_combined_steps(a: &[Vec2d], b: &[Vec2d]) -> Result<Option<u64>, AocError> {
+    match best_steps_crossing(a, b)? {
+        Some(crossing) => crossing.combined_steps().map(Some),
+        None => Ok(None),
+    }
+}
+
+// Returns the distance to the closest intersection and the fewest combined
+// steps to reach an intersection. The second wire is checked against the first
+// one as it is walked, so each wire is only traversed once. Later visits by the
+// second wire never lower either answer and need no special treatment. Adding
+// up steps that overflow is an error.
+pub fn solve(a: &[Vec2d], b: &[Vec2d]) -> Result<Option<(i32, u64)>, AocError> {
+    solve_from(a, b, Vec2d::ORIGIN)
+}
+
+// Same as solve, for wires leaving a central port at origin. Distances are
+// measured from the port, and the port itself does not count as a crossing.
+pub fn solve_from(a: &[Vec2d], b: &[Vec2d], origin: Vec2d) -> Result<Option<(i32, u64)>, AocError> {
+    solve_streaming(&first_visit_steps(a), (1..).zip(b.iter().copied()), origin)
+}
+
+// Same as solve, but only the first wire is held in memory. The second wire is
+// traced from its vertices one point at a time.
+pub fn solve_low_memory(a: &[Vec2d], b_vertices: &[Vec2d]) -> Result<Option<(i32, u64)>, AocError> {
+    solve_streaming(&first_visit_steps(a), trace(b_vertices), Vec2d::ORIGIN)
+}
+
+fn solve_streaming<I>(
+    steps_a: &HashMap<&Vec2d, u64>,
+    b: I,
+    origin: Vec2d,
+) -> Result<Option<(i32, u64)>, AocError>
+where
+    I: Iterator<Item = (u64, Vec2d)>,
+{
+    let mut answers: Option<(i32, u64)> = None;
+
+    for (step_b, point) in b.filter(|(_, point)| *point != origin) {
+        if let Some(step_a) = steps_a.get(&point) {
+            let distance = point.manhattan_distance_to(origin);
+            let steps = combined_steps(*step_a, step_b)?;
+            answers = Some(match answers {
+                Some((d, s)) => (min(d, distance), min(s, steps)),
+                None => (distance, steps),
+            });
+        }
+    }
+    Ok(answers)
+}
+
+// A straight piece of wire together with the number of steps needed to reach
+// its start.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Segment {
+    pub start: Vec2d,
+    pub end: Vec2d,
+    pub steps: u64,
+}
+
+impl Segment {
+    fn steps_to(&self, point: Vec2d) -> u64 {
+        self.steps + point.manhattan_distance_to(self.start) as u64
+    }
+
+    // The lower left and upper right corners of the segment.
+    fn bounds(&self) -> (Vec2d, Vec2d) {
+        (
+            Vec2d::new(min(self.start.x, self.end.x), min(self.start.y, self.end.y)),
+            Vec2d::new(max(self.start.x, self.end.x), max(self.start.y, self.end.y)),
+        )
+    }
+
+    // The corners of the box both segments lie in, or None when they are apart.
+    fn shared_bounds(&self, other: &Segment) -> Option<(Vec2d, Vec2d)> {
+        let (low_a, high_a) = self.bounds();
+        let (low_b, high_b) = other.bounds();
+        let low = Vec2d::new(max(low_a.x, low_b.x), max(low_a.y, low_b.y));
+        let high = Vec2d::new(min(high_a.x, high_b.x), min(high_a.y, high_b.y));
+        if low.x <= high.x && low.y <= high.y {
+            Some((low, high))
+        } else {
+            None
+        }
+    }
+
+    // Points shared with another segment. Both segments are axis-aligned, so the
+    // overlap of their bounding boxes is either empty, a single point where they
+    // cross or a run of points where they are collinear.
+    fn overlap(&self, other: &Segment) -> Vec<Vec2d> {
+        let mut points = Vec::new();
+        if let Some((low, high)) = self.shared_bounds(other) {
+            for x in low.x..=high.x {
+                for y in low.y..=high.y {
+                    points.push(Vec2d { x, y });
+                }
+            }
+        }
+        points
+    }
+}
+
+pub fn segments(vertices: &[Vec2d]) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut start = Vec2d::ORIGIN;
+    let mut steps = 0;
+    for vertex in vertices.iter() {
+        let length = axis_step(*vertex).map_or(0, |(_, length)| length);
+        let end = start + *vertex;
+        segments.push(Segment { start, end, steps });
+        steps += length as u64;
+        start = end;
+    }
+    segments
+}
+
+// Maps each point where the wires cross to the first-visit steps of both wires.
+pub fn segment_intersections(a: &[Segment], b: &[Segment]) -> HashMap<Vec2d, (u64, u64)> {
+    let mut intersections: HashMap<Vec2d, (u64, u64)> = HashMap::new();
+
+    for segment_a in a.iter() {
+        for segment_b in b.iter() {
+            for point in segment_a.overlap(segment_b) {
+                if point == Vec2d::ORIGIN {
+                    continue;
+                }
+                let steps_a = segment_a.steps_to(point);
+                let steps_b = segment_b.steps_to(point);
+                let steps = intersections.entry(point).or_insert((steps_a, steps_b));
+                steps.0 = min(steps.0, steps_a);
+                steps.1 = min(steps.1, steps_b);
+            }
+        }
+    }
+    intersections
+}
+
+// Counts the distinct points where two wires cross.
+pub fn intersection_count(a: &[Vec2d], b: &[Vec2d]) -> usize {
+    intersections(a, b).len()
+}
+
+// Counts the pairs of segments, one from each wire, that cross or run along
+// each other. A collinear overlap is a single event however many points it
+// covers. The start of a segment belongs to the segment before it, so a wire
+// turning on top of the other one is not counted twice.
+pub fn crossing_event_count(a: &[Segment], b: &[Segment]) -> usize {
+    let mut count = 0;
+    for segment_a in a.iter() {
+        for segment_b in b.iter() {
+            if segment_a
+                .overlap(segment_b)
+                .iter()
+                .any(|point| *point != segment_a.start && *point != segment_b.start)
+            {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+pub fn closest_intersection_distance_by_segments(a: &[Segment], b: &[Segment]) -> Option<i32> {
+    segment_intersections(a, b)
+        .keys()
+        .map(|point| point.manhattan_distance())
+        .min()
+}
+
+pub fn fewest_combined_steps_by_segments(
+    a: &[Segment],
+    b: &[Segment],
+) -> Result<Option<u64>, AocError> {
+    let mut fewest = None;
+    for (steps_a, steps_b) in segment_intersections(a, b).values() {
+        let steps = combined_steps(*steps_a, *steps_b)?;
+        fewest = Some(fewest.map_or(steps, |fewest| min(fewest, steps)));
+    }
+    Ok(fewest)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_path_error_test() {
+        assert_eq!(
+            parse("R8,U5,X5,D3"),
+            Err(ParseSegmentError::UnknownDirection("X".to_string()))
+        );
+        assert_eq!(parse("R8,R0,U5"), Err(ParseSegmentError::ZeroMagnitude));
+    }
+
+    #[test]
+    fn parse_path_test() {
+        assert_eq!(
+            parse("R8,U5,L5,D3"),
+            Ok(vec![
+                Vec2d { x: 8, y: 0 },
+                Vec2d { x: 0, y: 5 },
+                Vec2d { x: -5, y: 0 },
+                Vec2d { x: 0, y: -3 }
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_mixed_case_path_test() {
+        assert_eq!(parse("r8,U5,l5,d3"), parse("R8,U5,L5,D3"));
+        assert_eq!("r8,U5,l5,d3".parse::<Wire>(), "R8,U5,L5,D3".parse::<Wire>());
+    }
+
+    #[test]
+    fn parse_wires_test() {
+        let expected = vec![parse("R8,U5,L5,D3").unwrap(), parse("U7,R6,D4,L4").unwrap()];
+
+        assert_eq!(
+            parse_wires(&["R8,U5,L5,D3", "U7,R6,D4,L4"]).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_wires(&["R8,U5,L5,D3\r", "U7,R6,D4,L4\r"]).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_wires(&["R8,U5,L5,D3  ", "\tU7,R6,D4,L4 \r"]).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_wires(&["", "R8,U5,L5,D3", " \r", "U7,R6,D4,L4", ""]).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_wires(
+                &"R8,U5,L5,D3\r\nU7,R6,D4,L4\r\n"
+                    .split('\n')
+                    .collect::<Vec<_>>()
+            )
+            .unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn parse_spaced_path_test() {
+        assert_eq!(parse("R8, U5, L5, D3"), parse("R8,U5,L5,D3"));
+        assert_eq!(parse(" R8 ,U5 ,\tL5,  D3 "), parse("R8,U5,L5,D3"));
+        assert_eq!(
+            "R8, U5, L5, D3".parse::<Wire>().unwrap().to_string(),
+            "R8,U5,L5,D3"
+        );
+        assert_eq!(
+            parse("R8, U 5, L5"),
+            Err(ParseSegmentError::InvalidMagnitude(" 5".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_wires_comments_test() {
+        let lines = [
+            "# The first published example.",
+            "R8, U5, L5, D3",
+            "  # Comments may be indented.",
+            "",
+            "U7, R6, D4, L4",
+        ];
+        assert_eq!(
+            parse_wires(&lines).unwrap(),
+            vec![parse("R8,U5,L5,D3").unwrap(), parse("U7,R6,D4,L4").unwrap()]
+        );
+        assert!(matches!(
+            parse_wires(&["# R8,U5", "R8, U5, X5"]),
+            Err(AocError::Segment(ParseSegmentError::UnknownDirection(_)))
+        ));
+    }
+
+    #[test]
+    fn parse_wires_error_test() {
+        assert!(matches!(
+            parse_wires(&["R8,U5,L5,D3", "U7,R6,D,L4"]),
+            Err(AocError::Segment(ParseSegmentError::MissingMagnitude))
+        ));
+    }
+
+    #[test]
+    fn parse_labeled_wires_test() {
+        let wires = parse_labeled_wires(&["A: R8,U5,L5,D3", "# B: R1", "U7,R6,D4,L4", ""]).unwrap();
+        assert_eq!(
+            wires,
+            vec![
+                LabeledWire {
+                    name: "A".to_string(),
+                    vertices: parse("R8,U5,L5,D3").unwrap()
+                },
+                LabeledWire {
+                    name: "wire1".to_string(),
+                    vertices: parse("U7,R6,D4,L4").unwrap()
+                },
+            ]
+        );
+        assert_eq!(
+            parse_wires(&["A: R8,U5,L5,D3", "B:U7,R6,D4,L4"]).unwrap(),
+            parse_wires(&["R8,U5,L5,D3", "U7,R6,D4,L4"]).unwrap()
+        );
+
+        let wires =
+            parse_labeled_wires(&["red: R8,U5,L5,D3", "blue: U7,R6,D4,L4", "R2,U1"]).unwrap();
+        assert_eq!(
+            labeled_pairwise_intersections(&wires),
+            vec![
+                (Vec2d::new(3, 3), "red", "blue"),
+                (Vec2d::new(6, 5), "red", "blue"),
+                (Vec2d::new(1, 0), "red", "wire2"),
+                (Vec2d::new(2, 0), "red", "wire2"),
+            ]
+        );
+
+        assert!(matches!(
+            parse_labeled_wires(&["A: R8,Q5"]),
+            Err(AocError::Segment(ParseSegmentError::UnknownDirection(_)))
+        ));
+    }
+
+    #[test]
+    fn check_wires_test() {
+        assert_eq!(
+            check_wires(&["R8,U5,L5,D3", "", "# comment", "A: U7,R6"]).unwrap(),
+            vec![4, 2]
+        );
+        assert_eq!(check_wires::<&str>(&[]).unwrap(), vec![]);
+
+        let e = check_wires(&["R8,U5,L5,D3", "# comment", "U7,R6,X4"]).unwrap_err();
+        assert!(matches!(
+            e,
+            AocError::Line { line: 3, ref source }
+                if matches!(**source, AocError::Segment(ParseSegmentError::UnknownDirection(_)))
+        ));
+        assert_eq!(
+            e.to_string(),
+            "line 3: invalid wire: unknown direction: \"X\""
+        );
+    }
+
+    #[test]
+    fn duplicate_wires_test() {
+        let wires = parse_wires(&["R8,U5,L5,D3", "U7,R6,D4,L4", "R8,U5,L5,D3"]).unwrap();
+        assert_eq!(duplicate_wires(&wires), vec![(0, 2)]);
+
+        // A third copy is paired with the first wire, not the second copy.
+        let wires = parse_wires(&["R8,U5,L5,D3", "R8,U5,L5,D3", "R8,U5,L5,D3"]).unwrap();
+        assert_eq!(duplicate_wires(&wires), vec![(0, 1), (0, 2)]);
+
+        // Wires entering the same points through different segments differ.
+        let wires = parse_wires(&["R8,U5", "R4,R4,U5"]).unwrap();
+        assert_eq!(duplicate_wires(&wires), vec![]);
+        assert_eq!(duplicate_wires(&[]), vec![]);
+    }
+
+    #[test]
+    fn wire_round_trip_test() {
+        for path in ["R8,U5,L5,D3", "U62,R66,U55,R34,D71,R55,D58,R83", "L1"].iter() {
+            let wire = path.parse::<Wire>().unwrap();
+            assert_eq!(wire.to_string(), *path);
+        }
+
+        let wire = "R8,U5,L5,D3".parse::<Wire>().unwrap();
+        assert_eq!(wire.vertices(), parse("R8,U5,L5,D3").unwrap());
+        assert_eq!(
+            wire.points().collect::<Vec<Vec2d>>(),
+            get_points(&wire.vertices())
+        );
+    }
+
+    #[test]
+    fn passes_through_test() {
+        let wire = "R8,U5,L5,D3".parse::<Wire>().unwrap();
+        assert!(wire.visited.get().is_none());
+
+        assert!(wire.passes_through(Vec2d { x: 8, y: 5 }));
+        assert!(wire.passes_through(Vec2d { x: 3, y: 3 }));
+        assert!(!wire.passes_through(Vec2d { x: 4, y: 4 }));
+        assert!(!wire.passes_through(Vec2d::ORIGIN));
+        assert_eq!(wire.visited.get().map(|visited| visited.len()), Some(21));
+
+        // The central port is left out for a wire coming back through it too.
+        let wire = "R2,U2,L2,D2".parse::<Wire>().unwrap();
+        assert!(wire.passes_through(Vec2d { x: 0, y: 1 }));
+        assert!(!wire.passes_through(Vec2d::ORIGIN));
+
+        // Collecting the points does not change what the wire equals.
+        assert_eq!(wire, "R2,U2,L2,D2".parse::<Wire>().unwrap());
+    }
+
+    #[test]
+    fn wire_from_str_error_test() {
+        assert_eq!(
+            "R8,U5,X5,D3".parse::<Wire>(),
+            Err(ParseSegmentError::UnknownDirection("X".to_string()))
+        );
+        assert_eq!(
+            "R8,U".parse::<Wire>(),
+            Err(ParseSegmentError::MissingMagnitude)
+        );
+        assert_eq!(
+            "R8,U0,L5".parse::<Wire>(),
+            Err(ParseSegmentError::ZeroMagnitude)
+        );
+    }
+
+    #[test]
+    fn get_points_test() {
+        assert_eq!(
+            get_points(&parse("R8,U5,L5,D3").unwrap()),
+            vec![
+                Vec2d { x: 1, y: 0 },
+                Vec2d { x: 2, y: 0 },
+                Vec2d { x: 3, y: 0 },
+                Vec2d { x: 4, y: 0 },
+                Vec2d { x: 5, y: 0 },
+                Vec2d { x: 6, y: 0 },
+                Vec2d { x: 7, y: 0 },
+                Vec2d { x: 8, y: 0 },
+                Vec2d { x: 8, y: 1 },
+                Vec2d { x: 8, y: 2 },
+                Vec2d { x: 8, y: 3 },
+                Vec2d { x: 8, y: 4 },
+                Vec2d { x: 8, y: 5 },
+                Vec2d { x: 7, y: 5 },
+                Vec2d { x: 6, y: 5 },
+                Vec2d { x: 5, y: 5 },
+                Vec2d { x: 4, y: 5 },
+                Vec2d { x: 3, y: 5 },
+                Vec2d { x: 3, y: 4 },
+                Vec2d { x: 3, y: 3 },
+                Vec2d { x: 3, y: 2 }
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "not axis-aligned")]
+    fn get_points_diagonal_test() {
+        get_points(&[Vec2d { x: 2, y: 0 }, Vec2d { x: 1, y: 1 }]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not axis-aligned")]
+    fn segments_diagonal_test() {
+        segments(&[Vec2d { x: 2, y: 0 }, Vec2d { x: -1, y: 3 }]);
+    }
+
+    #[test]
+    fn step_field_test() {
+        let field = step_field(&parse("R8,U5").unwrap());
+
+        assert_eq!(field.len(), 13);
+        for x in 1..=8 {
+            assert_eq!(field[&Vec2d::new(x, 0)], x as u64);
+        }
+        for y in 1..=5 {
+            assert_eq!(field[&Vec2d::new(8, y)], 8 + y as u64);
+        }
+        assert!(!field.contains_key(&Vec2d::ORIGIN));
+
+        // Squares visited again keep the steps of the first visit.
+        let vertices = parse("R8,U5,L5,D3,R6").unwrap();
+        let field = step_field(&vertices);
+        assert_eq!(field[&Vec2d::new(8, 2)], 10);
+        let points = get_points(&vertices);
+        let first_visits = first_visit_steps(&points);
+        assert_eq!(field.len(), first_visits.len());
+        for (point, steps) in first_visits {
+            assert_eq!(field[point], steps);
+        }
+    }
+
+    #[test]
+    fn trace_test() {
+        let vertices = parse("R8,U5,L5,D3").unwrap();
+        let traced: Vec<(u64, Vec2d)> = trace(&vertices).collect();
+
+        assert_eq!(traced.len(), 21);
+        assert_eq!(traced[0], (1, Vec2d { x: 1, y: 0 }));
+        assert_eq!(traced[8], (9, Vec2d { x: 8, y: 1 }));
+        assert_eq!(traced[20], (21, Vec2d { x: 3, y: 2 }));
+        assert_eq!(
+            traced
+                .iter()
+                .map(|(_, point)| *point)
+                .collect::<Vec<Vec2d>>(),
+            get_points(&vertices)
+        );
+        for ((step, _), i) in traced.iter().zip(0..) {
+            assert_eq!(*step, i + 1);
+        }
+    }
+
+    #[test]
+    fn total_length_test() {
+        let vertices = parse("R8,U5,L5,D3").unwrap();
+        assert_eq!(total_length(&vertices), 21);
+        assert_eq!(total_length(&vertices), get_points(&vertices).len() as i64);
+        assert_eq!(total_length(&[]), 0);
+
+        let long = parse("R2147483647,U5,R2147483647").unwrap();
+        assert_eq!(total_length(&long), 4_294_967_299);
+    }
+
+    #[test]
+    fn endpoint_test() {
+        let vertices = parse("R8,U5,L5,D3").unwrap();
+        assert_eq!(endpoint(&vertices), WideVec2d::new(3, 2));
+        assert_eq!(
+            endpoint(&vertices),
+            WideVec2d::from(*get_points(&vertices).last().unwrap())
+        );
+        assert_eq!(
+            endpoint(&parse("U7,R6,D4,L4").unwrap()),
+            WideVec2d::new(2, 3)
+        );
+        assert_eq!(endpoint(&[]), WideVec2d::ORIGIN);
+
+        // Each segment fits in an i32, but the wire ends past i32::MAX.
+        let long = parse("R2147483647,U5,R2147483647,L3").unwrap();
+        assert_eq!(endpoint(&long), WideVec2d::new(4_294_967_291, 5));
+    }
+
+    #[test]
+    fn normalize_test() {
+        let vertices = parse("R5,R5,U3").unwrap();
+        let normalized = normalize(&vertices);
+
+        assert_eq!(normalized, parse("R10,U3").unwrap());
+        assert_eq!(
+            trace(&normalized).collect::<Vec<_>>(),
+            trace(&vertices).collect::<Vec<_>>()
+        );
+
+        // Turning back is not the same direction and is kept apart.
+        assert_eq!(
+            normalize(&parse("U2,D1,D4,L1,L2,L3").unwrap()),
+            parse("U2,D5,L6").unwrap()
+        );
+        let vertices = vec![Vec2d::new(0, 2), Vec2d::ORIGIN, Vec2d::new(0, 1)];
+        assert_eq!(normalize(&vertices), vec![Vec2d::new(0, 3)]);
+        assert_eq!(normalize(&[]), vec![]);
+    }
+
+    #[test]
+    fn turns_test() {
+        let a = parse("R8,U5,L5,D3").unwrap();
+        assert_eq!(turns(&a), vec![TurnKind::Left; 3]);
+        let b = parse("U7,R6,D4,L4").unwrap();
+        assert_eq!(turns(&b), vec![TurnKind::Right; 3]);
+
+        let vertices = parse("R2,U2,R2,D2,D1,L1,R3").unwrap();
+        assert_eq!(
+            turns(&vertices),
+            vec![
+                TurnKind::Left,
+                TurnKind::Right,
+                TurnKind::Right,
+                TurnKind::Right
+            ]
+        );
+        assert_eq!(turn_count(&vertices), 4);
+        assert_eq!(turn_count(&parse("R5").unwrap()), 0);
+        assert_eq!(turn_count(&[]), 0);
+    }
+
+    #[test]
+    fn crossings_test() {
+        let a = get_points(&parse("R8,U5,L5,D3").unwrap());
+        let b = get_points(&parse("U7,R6,D4,L4").unwrap());
+        let crossings = crossings(&a, &b);
+
+        assert_eq!(
+            crossings,
+            vec![
+                Crossing {
+                    point: Vec2d { x: 3, y: 3 },
+                    steps_a: 20,
+                    steps_b: 20
+                },
+                Crossing {
+                    point: Vec2d { x: 6, y: 5 },
+                    steps_a: 15,
+                    steps_b: 15
+                }
+            ]
+        );
+        assert_eq!(crossings.first().unwrap().point.manhattan_distance(), 6);
+        assert_eq!(
+            crossings
+                .iter()
+                .min_by_key(|c| c.steps_a + c.steps_b)
+                .map(|c| c.steps_a + c.steps_b),
+            Some(30)
+        );
+    }
+
+    #[test]
+    fn crossings_iter_test() {
+        let a = parse("R75,D30,R83,U83,L12,D49,R71,U7,L72").unwrap();
+        let b = parse("U62,R66,U55,R34,D71,R55,D58,R83").unwrap();
+        let (segments_a, segments_b) = (segments(&a), segments(&b));
+        let (points_a, points_b) = (get_points(&a), get_points(&b));
+
+        let first = crossings_iter(&segments_a, &segments_b).next().unwrap();
+        assert_eq!(first.point.manhattan_distance(), 159);
+        assert_eq!(
+            Some(first.point),
+            closest_intersection(&points_a, &points_b)
+        );
+        assert_eq!(
+            crossings_iter(&segments_a, &segments_b).collect::<Vec<_>>(),
+            crossings(&points_a, &points_b)
+        );
+        assert_eq!(
+            crossings_iter(&segments_a, &segments_b).find(|c| c.point.manhattan_distance() > 159),
+            crossings(&points_a, &points_b).get(1).copied()
+        );
+    }
+
+    #[test]
+    fn crossings_iter_agrees_with_crossings_test() {
+        // Overlapping runs, wires coming back through the port and through
+        // crossings, and ties in distance.
+        let test_cases = [
+            ("R8,U5,L5,D3", "U7,R6,D4,L4"),
+            ("R4,U2,L2,D2", "D1,R2,U1"),
+            ("U1,R6", "R2,U1,R3,L2"),
+            ("R1,U1,L1,D1", "U1,R1,D1,L1,R2"),
+            ("R20,U2,L19,D4", "U1,R1"),
+            ("R2,U2,L2,D4,R4", "U1,L1,D2,R3,U4"),
+            ("R8,U5,L5,D3", "D1,R8"),
+        ];
+        for (a, b) in test_cases.iter() {
+            let (a, b) = (parse(a).unwrap(), parse(b).unwrap());
+            assert_eq!(
+                crossings_iter(&segments(&a), &segments(&b)).collect::<Vec<_>>(),
+                crossings(&get_points(&a), &get_points(&b)),
+                "{:?} {:?}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn crossings_within_test() {
+        let a = parse("R8,U5,L5,D3").unwrap();
+        let b = parse("U7,R6,D4,L4").unwrap();
+        let (segments_a, segments_b) = (segments(&a), segments(&b));
+        let points = |radius| {
+            crossings_within(&segments_a, &segments_b, radius)
+                .iter()
+                .map(|crossing| crossing.point)
+                .collect::<Vec<Vec2d>>()
+        };
+
+        assert_eq!(points(5), vec![]);
+        assert_eq!(points(6), vec![Vec2d::new(3, 3)]);
+        assert_eq!(points(10), vec![Vec2d::new(3, 3)]);
+        assert_eq!(points(11), vec![Vec2d::new(3, 3), Vec2d::new(6, 5)]);
+        assert_eq!(
+            crossings_within(&segments_a, &segments_b, i32::MAX),
+            crossings(&get_points(&a), &get_points(&b))
+        );
+
+        // The steps to a crossing near the port come from segments reaching
+        // it after the wire has been far out.
+        let a = parse("R20,U2,L19,D4").unwrap();
+        let b = parse("U1,R1").unwrap();
+        assert_eq!(
+            crossings_within(&segments(&a), &segments(&b), 2),
+            crossings(&get_points(&a), &get_points(&b))
+        );
+    }
+
+    #[test]
+    fn closest_distance_test() {
+        let test_cases = [
+            ("R8", 0),
+            ("U3,R4,D6", 4),
+            ("L2,D5,R4", 5),
+            ("U2,L3,D4", 3),
+            ("R2,U3,L4,D1", 4),
+        ];
+        for (path, expected) in test_cases.iter() {
+            let (low, high) = segments(&parse(path).unwrap()).last().unwrap().bounds();
+            assert_eq!(closest_distance(low, high), *expected, "{}", path);
+        }
+    }
+
+    #[test]
+    fn distance_stats_test() {
+        let a = get_points(&parse("R10").unwrap());
+        let b = get_points(&parse("U1,R2,D2,R2,U2,R2,D2").unwrap());
+        assert_eq!(
+            distance_stats(&a, &b),
+            Some(DistanceStats {
+                min: 2,
+                median: 4.0,
+                max: 6,
+                count: 3
+            })
+        );
+
+        let a = get_points(&parse("R8,U5,L5,D3").unwrap());
+        let b = get_points(&parse("U7,R6,D4,L4").unwrap());
+        assert_eq!(
+            distance_stats(&a, &b),
+            Some(DistanceStats {
+                min: 6,
+                median: 8.5,
+                max: 11,
+                count: 2
+            })
+        );
+
+        let c = get_points(&parse("D1,R8").unwrap());
+        assert_eq!(distance_stats(&a, &c), None);
+    }
+
+    #[test]
+    fn crossings_by_quadrant_test() {
+        let a = get_points(&parse("R8,U5,L5,D3").unwrap());
+        let b = get_points(&parse("U7,R6,D4,L4").unwrap());
+        assert_eq!(crossings_by_quadrant(&a, &b), [2, 0, 0, 0]);
+
+        // The second wire crosses the square drawn by the first one on the
+        // positive y axis at (0, 2), then at (-1, 2) and (-1, -2).
+        let a = get_points(&parse("R2,U2,L4,D4,R4").unwrap());
+        let b = get_points(&parse("U4,L1,D8").unwrap());
+        assert_eq!(crossings_by_quadrant(&a, &b), [0, 2, 1, 0]);
+
+        let a = get_points(&parse("R3,D3").unwrap());
+        let b = get_points(&parse("D2,R5").unwrap());
+        assert_eq!(crossings_by_quadrant(&a, &b), [0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn best_steps_crossing_test() {
+        let a = get_points(&parse("R8,U5,L5,D3").unwrap());
+        let b = get_points(&parse("U7,R6,D4,L4").unwrap());
+        let best = best_steps_crossing(&a, &b).unwrap().unwrap();
+
+        assert_eq!(best.point, Vec2d { x: 6, y: 5 });
+        assert_eq!((best.steps_a, best.steps_b), (15, 15));
+        assert_eq!(best.combined_steps().unwrap(), 30);
+
+        let c = get_points(&parse("D1,R8").unwrap());
+        assert_eq!(best_steps_crossing(&a, &c).unwrap(), None);
+    }
+
+    #[test]
+    fn classify_intersection_test() {
+        let a = get_points(&parse("R8,U5,L5,D3").unwrap());
+        let b = get_points(&parse("U7,R6,D4,L4").unwrap());
+        for point in sorted_intersections(&a, &b) {
+            assert_eq!(
+                classify_intersection(&a, &b, point),
+                Some(IntersectionKind::Crossing)
+            );
+        }
+
+        // The second wire comes down onto the first one and stops.
+        let a = get_points(&parse("R4").unwrap());
+        let b = get_points(&parse("U2,R2,D2").unwrap());
+        let t = Vec2d::new(2, 0);
+        assert_eq!(
+            classify_intersection(&a, &b, t),
+            Some(IntersectionKind::Touch)
+        );
+        assert_eq!(
+            classify_intersection(&b, &a, t),
+            Some(IntersectionKind::Touch)
+        );
+
+        // Turning onto the other wire's line of travel is an overlap.
+        let b = get_points(&parse("U2,R2,D2,R1").unwrap());
+        assert_eq!(
+            classify_intersection(&a, &b, t),
+            Some(IntersectionKind::Overlap)
+        );
+
+        // Two wires turning away from each other at a shared corner only touch.
+        let a = get_points(&parse("R2,U2").unwrap());
+        let b = get_points(&parse("D1,R3,U1,L1,D1").unwrap());
+        assert_eq!(
+            classify_intersection(&a, &b, Vec2d::new(2, 0)),
+            Some(IntersectionKind::Touch)
+        );
+
+        assert_eq!(classify_intersection(&a, &b, Vec2d::ORIGIN), None);
+        assert_eq!(classify_intersection(&a, &b, Vec2d::new(5, 5)), None);
+    }
+
+    #[test]
+    fn closest_intersection_excluding_test() {
+        let a = get_points(&parse("R8,U5,L5,D3").unwrap());
+        let b = get_points(&parse("U7,R6,D4,L4").unwrap());
+
+        assert_eq!(closest_intersection(&a, &b), Some(Vec2d { x: 3, y: 3 }));
+
+        let mut excluded = HashSet::new();
+        excluded.insert(Vec2d { x: 3, y: 3 });
+        assert_eq!(
+            closest_intersection_excluding(&a, &b, &excluded),
+            Some(Vec2d { x: 6, y: 5 })
+        );
+
+        excluded.insert(Vec2d { x: 6, y: 5 });
+        assert_eq!(closest_intersection_excluding(&a, &b, &excluded), None);
+    }
+
+    #[test]
+    fn closest_intersection_origin_test() {
+        // The second wire comes back through the central port, which the first
+        // wire also passes through on its way down. The port is left out even
+        // when nothing is excluded.
+        let a = get_points(&parse("R2,U2,L2,D4").unwrap());
+        let b = get_points(&parse("U1,L1,D1,R3,U2").unwrap());
+
+        assert_eq!(
+            closest_intersection_excluding(&a, &b, &HashSet::new()),
+            Some(Vec2d { x: 0, y: 1 })
+        );
+        assert_eq!(closest_intersection(&a, &b), Some(Vec2d { x: 0, y: 1 }));
+    }
+
+    #[test]
+    fn closest_intersection_euclidean_test() {
+        // (3, 3) is the closest crossing by either metric.
+        let a = get_points(&parse("R8,U5,L5,D3").unwrap());
+        let b = get_points(&parse("U7,R6,D4,L4").unwrap());
+        assert_eq!(closest_intersection_euclidean(&a, &b), Some(18f64.sqrt()));
+
+        // The wires cross at (3, 2) and (0, 4). The first one is closer in a
+        // straight line, the second one along the grid.
+        let a = get_points(&parse("U4,R3,D2").unwrap());
+        let b = get_points(&parse("R3,U2,R1,U3,L4,D1").unwrap());
+        assert_eq!(closest_intersection_distance(&a, &b), Some(4));
+        assert_eq!(closest_intersection_euclidean(&a, &b), Some(13f64.sqrt()));
+
+        assert_eq!(closest_intersection_euclidean(&a, &[]), None);
+    }
+
+    #[test]
+    fn faster_wire_test() {
+        // Both wires reach both crossings of the first example at the same time.
+        let a = get_points(&parse("R8,U5,L5,D3").unwrap());
+        let b = get_points(&parse("U7,R6,D4,L4").unwrap());
+        for crossing in crossings(&a, &b).iter() {
+            assert_eq!(crossing.faster_wire(), None);
+        }
+
+        // The first wire reaches (2, 0) after 2 steps, the second after 4.
+        let a = get_points(&parse("R4,U2,L2,D2").unwrap());
+        let b = get_points(&parse("D1,R2,U1").unwrap());
+        assert_eq!(crossings(&a, &b)[0].faster_wire(), Some(WireId::A));
+        assert_eq!(crossings(&b, &a)[0].faster_wire(), Some(WireId::B));
+
+        let a = get_points(&parse("R75,D30,R83,U83,L12,D49,R71,U7,L72").unwrap());
+        let b = get_points(&parse("U62,R66,U55,R34,D71,R55,D58,R83").unwrap());
+        let closest = crossings(&a, &b)[0];
+        assert_eq!(
+            closest,
+            Crossing {
+                point: Vec2d { x: 155, y: 4 },
+                steps_a: 341,
+                steps_b: 385
+            }
+        );
+        assert_eq!(closest.faster_wire(), Some(WireId::A));
+    }
+
+    #[test]
+    fn sorted_intersections_test() {
+        let a = get_points(&parse("R8,U5,L5,D3").unwrap());
+        let b = get_points(&parse("U7,R6,D4,L4").unwrap());
+        assert_eq!(
+            sorted_intersections(&a, &b),
+            vec![Vec2d { x: 3, y: 3 }, Vec2d { x: 6, y: 5 }]
+        );
+
+        // Ties on distance are broken on x and then y.
+        let a = get_points(&parse("U1,R6").unwrap());
+        let b = get_points(&parse("R2,U1,R3,L2").unwrap());
+        assert_eq!(
+            sorted_intersections(&a, &b),
+            vec![
+                Vec2d { x: 2, y: 1 },
+                Vec2d { x: 3, y: 1 },
+                Vec2d { x: 4, y: 1 },
+                Vec2d { x: 5, y: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn bounding_box_test() {
+        let points = get_points(&parse("R8,U5,L5,D3").unwrap());
+        assert_eq!(
+            bounding_box(&points),
+            (Vec2d { x: 0, y: 0 }, Vec2d { x: 8, y: 5 })
+        );
+        assert_eq!(span(&points), (9, 6));
+
+        let points = get_points(&parse("L3,D2,R1").unwrap());
+        assert_eq!(
+            bounding_box(&points),
+            (Vec2d { x: -3, y: -2 }, Vec2d { x: 0, y: 0 })
+        );
+        assert_eq!(span(&points), (4, 3));
+    }
+
+    #[test]
+    fn bounding_box_empty_test() {
+        let origin = Vec2d { x: 0, y: 0 };
+        assert_eq!(bounding_box(&[]), (origin, origin));
+        assert_eq!(span(&[]), (1, 1));
+    }
+
+    #[test]
+    fn shortest_path_avoiding_test() {
+        let start = Vec2d::new(0, 0);
+        let end = Vec2d::new(4, 0);
+        assert_eq!(shortest_path_avoiding(&HashSet::new(), start, end), Some(4));
+        assert_eq!(
+            shortest_path_avoiding(&HashSet::new(), start, start),
+            Some(0)
+        );
+
+        // A wall at x = 2 from y = -1 to y = 3 forces a detour below it.
+        let wall: HashSet<Vec2d> = (-1..=3).map(|y| Vec2d::new(2, y)).collect();
+        assert_eq!(shortest_path_avoiding(&wall, start, end), Some(8));
+
+        // The first wire of the first example is walked around below, where
+        // the central port leaves a gap.
+        let wire: HashSet<Vec2d> = get_points(&parse("R8,U5,L5,D3").unwrap())
+            .into_iter()
+            .collect();
+        assert_eq!(
+            shortest_path_avoiding(&wire, Vec2d::new(0, 1), Vec2d::new(4, 4)),
+            Some(7)
+        );
+        assert_eq!(
+            shortest_path_avoiding(&wire, Vec2d::new(0, 1), Vec2d::new(9, 1)),
+            Some(13)
+        );
+        assert_eq!(shortest_path_avoiding(&wire, start, end), None);
+
+        let ring: HashSet<Vec2d> = Vec2d::new(5, 5).neighbors8().iter().copied().collect();
+        assert_eq!(shortest_path_avoiding(&ring, start, Vec2d::new(5, 5)), None);
+    }
+
+    #[test]
+    fn self_intersections_test() {
+        // A figure-eight crossing itself at (1, 0), closing its lower loop
+        // through the central port.
+        let wire = get_points(&parse("R2,U2,L1,D4,L1,U2").unwrap());
+        assert_eq!(self_intersections(&wire), vec![Vec2d { x: 1, y: 0 }]);
+
+        // (1, 1) is visited three times but only reported once.
+        let wire = get_points(&parse("R1,U2,L1,D1,R2,D1,L1,U2").unwrap());
+        assert_eq!(
+            self_intersections(&wire),
+            vec![
+                Vec2d { x: 1, y: 1 },
+                Vec2d { x: 1, y: 0 },
+                Vec2d { x: 1, y: 2 }
+            ]
+        );
+
+        let wire = get_points(&parse("R8,U5,L5,D3").unwrap());
+        assert_eq!(self_intersections(&wire), vec![]);
+    }
+
+    #[test]
+    fn combined_steps_test() {
+        assert_eq!(combined_steps(15, 15).unwrap(), 30);
+        assert_eq!(combined_steps(u64::MAX - 1, 1).unwrap(), u64::MAX);
+        assert!(matches!(
+            combined_steps(u64::MAX, 1),
+            Err(AocError::Overflow("combined steps"))
+        ));
+        assert!(matches!(
+            combined_steps(1 << 63, 1 << 63),
+            Err(AocError::Overflow(_))
+        ));
+
+        // Every path adding up steps goes through the same check.
+        let crossing = Crossing {
+            point: Vec2d::new(1, 0),
+            steps_a: u64::MAX,
+            steps_b: 1,
+        };
+        assert!(matches!(
+            crossing.combined_steps(),
+            Err(AocError::Overflow(_))
+        ));
+        let point = Vec2d::new(1, 0);
+        let steps_a: HashMap<&Vec2d, u64> = iter::once((&point, u64::MAX)).collect();
+        assert!(matches!(
+            solve_streaming(&steps_a, iter::once((1, point)), Vec2d::ORIGIN),
+            Err(AocError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn covered_cell_count_test() {
+        // 21 points each, two of them shared.
+        let wires = vec![
+            get_points(&parse("R8,U5,L5,D3").unwrap()),
+            get_points(&parse("U7,R6,D4,L4").unwrap()),
+        ];
+        assert_eq!(covered_cell_count(&wires), 40);
+        assert_eq!(covered_cell_count(&wires[..1]), 21);
+
+        // A loop back through the central port leaves it out of its 8 points.
+        let wires = vec![get_points(&parse("R2,U2,L2,D2").unwrap())];
+        assert_eq!(covered_cell_count(&wires), 7);
+        assert_eq!(covered_cell_count(&[]), 0);
+    }
+
+    #[test]
+    fn closest_multi_wire_crossing_test() {
+        // All three wires pass through (2, 2); the last two also meet further
+        // out at (3, 2).
+        let wires: Vec<Vec<Vec2d>> = ["R2,U4", "U2,R4", "D1,R3,U3,L1"]
+            .iter()
+            .map(|path| get_points(&parse(path).unwrap()))
+            .collect();
+        assert_eq!(
+            closest_multi_wire_crossing(&wires),
+            Some((Vec2d::new(2, 2), vec![0, 1, 2]))
+        );
+
+        // A wire going back over itself does not meet another wire.
+        let wires: Vec<Vec<Vec2d>> = ["R2,L1,R1,U1", "U1,R3"]
+            .iter()
+            .map(|path| get_points(&parse(path).unwrap()))
+            .collect();
+        assert_eq!(
+            closest_multi_wire_crossing(&wires),
+            Some((Vec2d::new(2, 1), vec![0, 1]))
+        );
+        assert_eq!(closest_multi_wire_crossing(&wires[..1]), None);
+        assert_eq!(closest_multi_wire_crossing(&[]), None);
+    }
+
+    #[test]
+    fn fewest_pairwise_steps_first_visit_test() {
+        // The first wire passes (2, 0) after 2 steps and again after 10 steps,
+        // only the first visit should count.
+        let wires = vec![
+            get_points(&parse("R4,U2,L2,D2").unwrap()),
+            get_points(&parse("D1,R2,U1").unwrap()),
+        ];
+        let intersections = all_pairwise_intersections(&wires);
+
+        assert_eq!(intersections, vec![(Vec2d { x: 2, y: 0 }, 0, 1)]);
+        assert_eq!(
+            fewest_pairwise_steps(&wires, &intersections).unwrap(),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn three_wires_test() {
+        // The third wire crosses itself at (0, -1), which must not count.
+        let wires = vec![
+            get_points(&parse("R8,U5,L5,D3").unwrap()),
+            get_points(&parse("U7,R6,D4,L4").unwrap()),
+            get_points(&parse("D2,R2,U1,L3,U3,R12").unwrap()),
+        ];
+        let mut intersections = all_pairwise_intersections(&wires);
+        intersections.sort_by_key(|(point, i, j)| (*i, *j, point.x, point.y));
+
+        assert_eq!(
+            intersections,
+            vec![
+                (Vec2d { x: 3, y: 3 }, 0, 1),
+                (Vec2d { x: 6, y: 5 }, 0, 1),
+                (Vec2d { x: 3, y: 2 }, 0, 2),
+                (Vec2d { x: 8, y: 2 }, 0, 2),
+                (Vec2d { x: 0, y: 2 }, 1, 2),
+            ]
+        );
+        assert_eq!(closest_pairwise_distance(&intersections), Some(2));
+        assert_eq!(
+            fewest_pairwise_steps(&wires, &intersections).unwrap(),
+            Some(14)
+        );
+    }
+
+    #[test]
+    fn solve_pairwise_test() {
+        let wires = vec![
+            get_points(&parse("R8,U5,L5,D3").unwrap()),
+            get_points(&parse("U7,R6,D4,L4").unwrap()),
+        ];
+        assert_eq!(solve_pairwise(&wires).unwrap(), (6, 30));
+        assert_eq!(part_one(&wires).unwrap(), 6);
+        assert_eq!(part_two(&wires).unwrap(), 30);
+    }
+
+    #[test]
+    fn solve_pairwise_through_port_test() {
+        // Both wires come back through the central port, which is no crossing.
+        let wires = vec![
+            get_points(&parse("R1,U1,L1,D1").unwrap()),
+            get_points(&parse("U1,R1,D1,L1,R2").unwrap()),
+        ];
+        assert_eq!(
+            all_pairwise_intersections(&wires)
+                .iter()
+                .map(|(point, _, _)| *point)
+                .collect::<Vec<Vec2d>>(),
+            vec![Vec2d::new(0, 1), Vec2d::new(1, 0), Vec2d::new(1, 1)]
+        );
+        assert_eq!(part_one(&wires).unwrap(), 1);
+        assert_eq!(part_two(&wires).unwrap(), 4);
+        assert_eq!(solve_pairwise(&wires).unwrap(), (1, 4));
+        assert_eq!(solve(&wires[0], &wires[1]).unwrap(), Some((1, 4)));
+    }
+
+    #[test]
+    fn crossings_through_port_test() {
+        let a = get_points(&parse("R1,U1,L1,D1").unwrap());
+        let b = get_points(&parse("U1,R1,D1,L1,R2").unwrap());
+
+        assert_eq!(
+            crossings(&a, &b)
+                .iter()
+                .map(|crossing| crossing.point)
+                .collect::<Vec<Vec2d>>(),
+            vec![Vec2d::new(0, 1), Vec2d::new(1, 0), Vec2d::new(1, 1)]
+        );
+        assert_eq!(intersection_count(&a, &b), 3);
+        assert_eq!(closest_intersection_distance(&a, &b), Some(1));
+        assert_eq!(closest_intersection(&a, &b), Some(Vec2d::new(0, 1)));
+        assert_eq!(closest_intersection_euclidean(&a, &b), Some(1.0));
+        assert_eq!(distance_stats(&a, &b).map(|stats| stats.min), Some(1));
+        assert_eq!(
+            best_steps_crossing(&a, &b)
+                .unwrap()
+                .map(|crossing| crossing.point),
+            Some(Vec2d::new(0, 1))
+        );
+        assert_eq!(crossings_by_quadrant(&a, &b), [2, 1, 0, 0]);
+    }
+
+    #[test]
+    fn solve_pairwise_error_test() {
+        let mut wires = vec![get_points(&parse("R8,U5,L5,D3").unwrap())];
+        assert!(matches!(
+            solve_pairwise(&[]),
+            Err(AocError::InsufficientInput { found: 0, .. })
+        ));
+        let e = solve_pairwise(&wires).unwrap_err();
+        assert!(matches!(e, AocError::InsufficientInput { found: 1, .. }));
+        assert_eq!(e.to_string(), "expected at least 2 wires, found 1");
+
+        wires.push(get_points(&parse("D1,R8").unwrap()));
+        let e = solve_pairwise(&wires).unwrap_err();
+        assert!(matches!(e, AocError::NotFound(_)));
+        assert_eq!(e.to_string(), "no intersections found");
+    }
+
+    #[test]
+    fn published_examples_test() {
+        let test_cases = [
+            ("R8,U5,L5,D3", "U7,R6,D4,L4", 6, 30),
+            (
+                "R75,D30,R83,U83,L12,D49,R71,U7,L72",
+                "U62,R66,U55,R34,D71,R55,D58,R83",
+                159,
+                610,
+            ),
+            (
+                "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51",
+                "U98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+                135,
+                410,
+            ),
+        ];
+        for case in test_cases.iter() {
+            let a = get_points(&parse(case.0).unwrap());
+            let b = get_points(&parse(case.1).unwrap());
+            assert_eq!(closest_intersection_distance(&a, &b), Some(case.2));
+            assert_eq!(fewest_combined_steps(&a, &b).unwrap(), Some(case.3));
+            assert_eq!(solve(&a, &b).unwrap(), Some((case.2, case.3)));
+        }
+    }
+
+    #[test]
+    fn day_answers_test() {
+        let mut wires = vec![
+            get_points(&parse("R8,U5,L5,D3").unwrap()),
+            get_points(&parse("U7,R6,D4,L4").unwrap()),
+        ];
+        let answers = day_answers(&wires).unwrap();
+
+        assert_eq!(answers.day, 3);
+        assert_eq!(
+            (answers.part1.as_str(), answers.part2.as_str()),
+            ("6", "30")
+        );
+
+        // A third wire reaching (0, 7) after 9 steps, where the second wire
+        // has taken 7.
+        wires.push(get_points(&parse("L1,U7,R2").unwrap()));
+        let answers = day_answers(&wires).unwrap();
+        assert_eq!(
+            (answers.part1.as_str(), answers.part2.as_str()),
+            ("6", "16")
+        );
+
+        assert!(matches!(
+            day_answers(&wires[..1]),
+            Err(AocError::InsufficientInput { found: 1, .. })
+        ));
+        let wires = vec![wires.swap_remove(0), get_points(&parse("D1,R8").unwrap())];
+        assert!(matches!(
+            day_answers(&wires),
+            Err(AocError::NotFound("intersections"))
+        ));
+    }
+
+    #[test]
+    fn solve_agrees_with_crossings_test() {
+        let test_cases = [
+            ("R8,U5,L5,D3", "U7,R6,D4,L4"),
+            ("R4,U2,L2,D2", "D1,R2,U1"),
+            ("D1,R2,U1", "R4,U2,L2,D2"),
+            ("U1,R6", "R2,U1,R3,L2"),
+            ("R8,U5,L5,D3", "D1,R8"),
+        ];
+        for case in test_cases.iter() {
+            let a = get_points(&parse(case.0).unwrap());
+            let b = get_points(&parse(case.1).unwrap());
+            let expected = closest_intersection_distance(&a, &b)
+                .and_then(|distance| Some((distance, fewest_combined_steps(&a, &b).unwrap()?)));
+            assert_eq!(solve(&a, &b).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn solve_from_test() {
+        let a = parse("R8,U5,L5,D3").unwrap();
+        let b = parse("U7,R6,D4,L4").unwrap();
+        let origin = Vec2d { x: 5, y: -2 };
+        let (a, b) = (get_points_from(&a, origin), get_points_from(&b, origin));
+
+        assert_eq!(a[0], Vec2d { x: 6, y: -2 });
+        assert_eq!(solve_from(&a, &b, origin).unwrap(), Some((6, 30)));
+        // Measured from (0, 0) the crossings at (8, 1) and (11, 3) are further
+        // away, but the steps stay the same.
+        assert_eq!(solve(&a, &b).unwrap(), Some((9, 30)));
+    }
+
+    #[test]
+    fn solve_excludes_origin_test() {
+        // Both wires come back through the central port, which would otherwise
+        // be the closest crossing.
+        let a = get_points(&parse("R1,U1,L1,D1").unwrap());
+        let b = get_points(&parse("U1,R1,D1,L1,R2").unwrap());
+
+        assert!(a.contains(&Vec2d { x: 0, y: 0 }) && b.contains(&Vec2d { x: 0, y: 0 }));
+        assert_eq!(solve(&a, &b).unwrap(), Some((1, 4)));
+    }
+
+    #[test]
+    fn solve_low_memory_test() {
+        let test_cases = [
+            ("R8,U5,L5,D3", "U7,R6,D4,L4"),
+            (
+                "R75,D30,R83,U83,L12,D49,R71,U7,L72",
+                "U62,R66,U55,R34,D71,R55,D58,R83",
+            ),
+            (
+                "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51",
+                "U98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+            ),
+            ("D1,R2,U1", "R4,U2,L2,D2"),
+            ("R8,U5,L5,D3", "D1,R8"),
+        ];
+        for case in test_cases.iter() {
+            let a = get_points(&parse(case.0).unwrap());
+            let b_vertices = parse(case.1).unwrap();
+            assert_eq!(
+                solve_low_memory(&a, &b_vertices).unwrap(),
+                solve(&a, &get_points(&b_vertices)).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn segments_test() {
+        assert_eq!(
+            segments(&parse("R8,U5,L5,D3").unwrap()),
+            vec![
+                Segment {
+                    start: Vec2d { x: 0, y: 0 },
+                    end: Vec2d { x: 8, y: 0 },
+                    steps: 0
+                },
+                Segment {
+                    start: Vec2d { x: 8, y: 0 },
+                    end: Vec2d { x: 8, y: 5 },
+                    steps: 8
+                },
+                Segment {
+                    start: Vec2d { x: 8, y: 5 },
+                    end: Vec2d { x: 3, y: 5 },
+                    steps: 13
+                },
+                Segment {
+                    start: Vec2d { x: 3, y: 5 },
+                    end: Vec2d { x: 3, y: 2 },
+                    steps: 18
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn segments_agree_with_points_test() {
+        let test_cases = [
+            ("R8,U5,L5,D3", "U7,R6,D4,L4"),
+            (
+                "R75,D30,R83,U83,L12,D49,R71,U7,L72",
+                "U62,R66,U55,R34,D71,R55,D58,R83",
+            ),
+            (
+                "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51",
+                "U98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+            ),
+            ("R4,U2,L2,D2", "D1,R2,U1"),
+        ];
+        for case in test_cases.iter() {
+            let a = parse(case.0).unwrap();
+            let b = parse(case.1).unwrap();
+            let (points_a, points_b) = (get_points(&a), get_points(&b));
+            let (segments_a, segments_b) = (segments(&a), segments(&b));
+            assert_eq!(
+                closest_intersection_distance_by_segments(&segments_a, &segments_b),
+                closest_intersection_distance(&points_a, &points_b)
+            );
+            assert_eq!(
+                fewest_combined_steps_by_segments(&segments_a, &segments_b).unwrap(),
+                fewest_combined_steps(&points_a, &points_b).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn collinear_overlap_test() {
+        // The second wire runs along the first one for four cells and then
+        // doubles back over part of the shared run.
+        let a = parse("U1,R6").unwrap();
+        let b = parse("R2,U1,R3,L2").unwrap();
+        let expected = [
+            (Vec2d { x: 2, y: 1 }, 3, 3),
+            (Vec2d { x: 3, y: 1 }, 4, 4),
+            (Vec2d { x: 4, y: 1 }, 5, 5),
+            (Vec2d { x: 5, y: 1 }, 6, 6),
+        ];
+
+        let crossings: Vec<(Vec2d, u64, u64)> = crossings(&get_points(&a), &get_points(&b))
+            .iter()
+            .map(|c| (c.point, c.steps_a, c.steps_b))
+            .collect();
+        assert_eq!(crossings, expected);
+
+        let by_segments = segment_intersections(&segments(&a), &segments(&b));
+        assert_eq!(by_segments.len(), expected.len());
+        for (point, steps_a, steps_b) in expected.iter() {
+            assert_eq!(by_segments[point], (*steps_a, *steps_b));
+        }
+    }
+
+    #[test]
+    fn crossing_event_count_test() {
+        let a = parse("R8,U5,L5,D3").unwrap();
+        let b = parse("U7,R6,D4,L4").unwrap();
+        assert_eq!(intersection_count(&get_points(&a), &get_points(&b)), 2);
+        assert_eq!(crossing_event_count(&segments(&a), &segments(&b)), 2);
+
+        // The second wire turns on top of the first one at (2, 1), runs along
+        // it and then doubles back over part of the shared run.
+        let a = parse("U1,R6").unwrap();
+        let b = parse("R2,U1,R3,L2").unwrap();
+        assert_eq!(intersection_count(&get_points(&a), &get_points(&b)), 4);
+        assert_eq!(crossing_event_count(&segments(&a), &segments(&b)), 3);
+
+        let b = parse("D1,R8").unwrap();
+        assert_eq!(intersection_count(&get_points(&a), &get_points(&b)), 0);
+        assert_eq!(crossing_event_count(&segments(&a), &segments(&b)), 0);
+    }
+}
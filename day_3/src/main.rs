@@ -108,210 +108,78 @@
 //
 // Both parts of this puzzle are complete! They provide two gold stars: **
 
-#[macro_use]
-extern crate lazy_static;
-extern crate regex;
-
-use regex::Regex;
-
-use std::collections::{HashMap, HashSet};
-use std::io;
-use std::io::prelude::*;
-
-use std::ops::Add;
-
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
-struct Vec2d {
-    x: i32,
-    y: i32,
-}
-
-impl Vec2d {
-    fn manhattan_distance(self) -> i32 {
-        self.x.abs() + self.y.abs()
-    }
-}
-
-impl Add for Vec2d {
-    type Output = Vec2d;
-
-    fn add(self, other: Vec2d) -> Self {
-        Vec2d {
-            x: self.x + other.x,
-            y: self.y + other.y,
+use common::args::Args;
+use common::input::read_lines_from_stdin_or_arg;
+use common::report::Report;
+use day_3::{best_steps_crossing, endpoint, get_points, parse_wires, part_one, part_two};
+use day_3::{check_wires, duplicate_wires, solve_pairwise, total_length};
+use day_3::{AocError, Vec2d};
+
+use std::process;
+use std::time::Instant;
+
+fn run(args: &Args) -> Result<(), AocError> {
+    let lines = read_lines_from_stdin_or_arg()?;
+    if args.check {
+        for (i, segments) in check_wires(&lines)?.iter().enumerate() {
+            println!("Wire {}: segments: {}", i + 1, segments);
         }
+        return Ok(());
     }
-}
 
-impl From<&str> for Vec2d {
-    fn from(s: &str) -> Vec2d {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"^([UDLR])(\d+$)").unwrap();
-        }
-        let captures = RE.captures(s).unwrap();
-        let direction = &captures[1];
-        let magnitude = captures[2].parse::<i32>().unwrap();
-        match direction {
-            "U" => Vec2d { x: 0, y: magnitude },
-            "D" => Vec2d {
-                x: 0,
-                y: -magnitude,
-            },
-            "L" => Vec2d {
-                x: -magnitude,
-                y: 0,
-            },
-            "R" => Vec2d { x: magnitude, y: 0 },
-            _ => panic!(""),
+    let vertices = parse_wires(&lines)?;
+    for (first, second) in duplicate_wires(&vertices) {
+        let e = AocError::DuplicateWire {
+            first: first + 1,
+            second: second + 1,
+        };
+        if args.strict {
+            return Err(e);
         }
+        eprintln!("warning: {}", e);
     }
-}
+    let wires: Vec<Vec<Vec2d>> = vertices.iter().map(|v| get_points(v)).collect();
 
-fn parse(path: &str) -> Vec<Vec2d> {
-    let mut points: Vec<Vec2d> = Vec::new();
-    for p in path.split(',') {
-        points.push(Vec2d::from(p));
+    if args.json {
+        let (distance, steps) = solve_pairwise(&wires)?;
+        println!("{{\"part1\": {}, \"part2\": {}}}", distance, steps);
+        return Ok(());
     }
-    points
-}
-
-fn get_points(vertices: &[Vec2d]) -> Vec<Vec2d> {
-    let mut points = Vec::new();
-    let mut pos = Vec2d { x: 0, y: 0 };
-    for vertex in vertices.iter() {
-        let mut direction = Vec2d { x: 0, y: 0 };
-        if vertex.x > 0 {
-            direction.x = 1;
-        } else if vertex.x < 0 {
-            direction.x = -1;
-        } else if vertex.y > 0 {
-            direction.y = 1;
-        } else if vertex.y < 0 {
-            direction.y = -1;
-        }
-        // We have no diagonal lines so one of the operands is always 0.
-        let line_length = vertex.x.abs() + vertex.y.abs();
 
-        for _ in 0..line_length {
-            pos = pos + direction;
-            points.push(pos);
+    let start = Instant::now();
+    let distance = part_one(&wires)?;
+    let part1 = format!("distance: {}{}", distance, args.timing(start.elapsed()));
+
+    let start = Instant::now();
+    let steps = part_two(&wires)?;
+    let elapsed = start.elapsed();
+    let mut part2 = format!("steps: {}", steps);
+    if let [a, b] = wires.as_slice() {
+        if let Some(best) = best_steps_crossing(a, b)? {
+            part2 += &format!(" at ({}, {})", best.point.x, best.point.y);
         }
     }
-    points
-}
-
-fn main() {
-    let mut wires: Vec<Vec<Vec2d>> = Vec::new();
-
-    for line in io::stdin().lock().lines() {
-        match line {
-            Ok(l) => wires.push(get_points(&parse(&l))),
-            Err(_) => panic!(),
-        }
-    }
-
-    let wire_one: HashSet<Vec2d> = wires[0].iter().cloned().collect();
-    let wire_two: HashSet<Vec2d> = wires[1].iter().cloned().collect();
-    let intersections: HashSet<&Vec2d> = wire_one.intersection(&wire_two).collect();
-
-    println!(
-        "Part 1: distance: {:?}",
-        intersections
-            .iter()
-            .map(|p| p.manhattan_distance())
-            .min()
-            .unwrap()
-    );
-
-    let mut steps: HashMap<&Vec2d, Vec<usize>> = HashMap::new();
-    for wire in wires.iter() {
-        for (step, point) in (1..).zip(wire.iter()) {
-            if intersections.contains(&point) {
-                match steps.get_mut(&point) {
-                    Some(v) => v.push(step),
-                    None => {
-                        steps.insert(&point, vec![step]);
-                    }
-                }
-            }
-        }
-    }
-    println!(
-        "Part 2: steps: {:?}",
-        steps
-            .values()
-            .map(|v| v.iter().sum::<usize>())
-            .min()
-            .unwrap()
-    );
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn point_from_str_test() {
-        let test_cases = [
-            ("R8", Vec2d { x: 8, y: 0 }),
-            ("U5", Vec2d { x: 0, y: 5 }),
-            ("L5", Vec2d { x: -5, y: 0 }),
-            ("D3", Vec2d { x: 0, y: -3 }),
-        ];
-        for case in test_cases.iter() {
-            assert_eq!(Vec2d::from(case.0), case.1);
-        }
-    }
-
-    #[test]
-    fn add_points_test() {
-        let a = Vec2d { x: 7, y: 3 };
-        let b = Vec2d { x: 3, y: 7 };
-        let expected = Vec2d { x: 10, y: 10 };
-
-        assert_eq!(a + b, expected);
-    }
-
-    #[test]
-    fn parse_path_test() {
-        assert_eq!(
-            parse("R8,U5,L5,D3"),
-            vec![
-                Vec2d { x: 8, y: 0 },
-                Vec2d { x: 0, y: 5 },
-                Vec2d { x: -5, y: 0 },
-                Vec2d { x: 0, y: -3 }
-            ]
+    part2 += &args.timing(elapsed);
+
+    Report { part1, part2 }.print();
+
+    for (i, wire) in vertices.iter().enumerate() {
+        let end = endpoint(wire);
+        println!(
+            "Wire {}: length: {}, end: ({}, {}), distance: {}",
+            i + 1,
+            total_length(wire),
+            end.x,
+            end.y,
+            end.manhattan_distance()
         );
     }
+    Ok(())
+}
 
-    #[test]
-    fn get_points_test() {
-        assert_eq!(
-            get_points(&parse("R8,U5,L5,D3")),
-            vec![
-                Vec2d { x: 1, y: 0 },
-                Vec2d { x: 2, y: 0 },
-                Vec2d { x: 3, y: 0 },
-                Vec2d { x: 4, y: 0 },
-                Vec2d { x: 5, y: 0 },
-                Vec2d { x: 6, y: 0 },
-                Vec2d { x: 7, y: 0 },
-                Vec2d { x: 8, y: 0 },
-                Vec2d { x: 8, y: 1 },
-                Vec2d { x: 8, y: 2 },
-                Vec2d { x: 8, y: 3 },
-                Vec2d { x: 8, y: 4 },
-                Vec2d { x: 8, y: 5 },
-                Vec2d { x: 7, y: 5 },
-                Vec2d { x: 6, y: 5 },
-                Vec2d { x: 5, y: 5 },
-                Vec2d { x: 4, y: 5 },
-                Vec2d { x: 3, y: 5 },
-                Vec2d { x: 3, y: 4 },
-                Vec2d { x: 3, y: 3 },
-                Vec2d { x: 3, y: 2 }
-            ]
-        );
+fn main() {
+    if let Err(e) = run(&Args::from_env()) {
+        eprintln!("{}", e);
+        process::exit(1);
     }
 }
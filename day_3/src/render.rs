@@ -0,0 +1,321 @@
+use crate::{all_pairwise_intersections, bounding_box, crossings, Vec2d, Wire};
+
+use std::cmp::{max, min};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::iter;
+use std::path::Path;
+
+// Images are shrunk so that neither side is longer than this many pixels.
+const MAX_IMAGE_SIDE: i32 = 1024;
+
+const BACKGROUND: [u8; 3] = [0, 0, 0];
+const WIRE_COLORS: [[u8; 3]; 2] = [[230, 60, 60], [60, 120, 230]];
+const CROSSING_COLOR: [u8; 3] = [250, 220, 40];
+const PORT_COLOR: [u8; 3] = [255, 255, 255];
+
+// Picks the symbol for a point on a wire from the moves into and out of it.
+fn symbol(previous: Vec2d, point: Vec2d, next: Option<&Vec2d>) -> char {
+    let horizontal_in = previous.y == point.y;
+    match next {
+        Some(next) if (next.y == point.y) != horizontal_in => '+',
+        _ if horizontal_in => '-',
+        _ => '|',
+    }
+}
+
+// Draws the wires like the puzzle text does, with +y pointing up. Only the
+// region occupied by the wires and the central port is rendered, so the grid
+// is translated to have the lower left corner of that region at its origin.
+pub fn render_grid(wires: &[Vec<Vec2d>]) -> String {
+    let origin = Vec2d::ORIGIN;
+    let (low, high) = wires.iter().map(|wire| bounding_box(wire)).fold(
+        (origin, origin),
+        |(low, high), (wire_low, wire_high)| {
+            (
+                Vec2d {
+                    x: min(low.x, wire_low.x),
+                    y: min(low.y, wire_low.y),
+                },
+                Vec2d {
+                    x: max(high.x, wire_high.x),
+                    y: max(high.y, wire_high.y),
+                },
+            )
+        },
+    );
+    let width = (high.x - low.x + 1) as usize;
+    let height = (high.y - low.y + 1) as usize;
+    // Rows are stored from the top down.
+    let index = |point: Vec2d| (high.y - point.y) as usize * width + (point.x - low.x) as usize;
+
+    let mut grid: Vec<Option<(usize, char)>> = vec![None; width * height];
+    for (id, wire) in wires.iter().enumerate() {
+        let mut previous = origin;
+        for (i, point) in wire.iter().enumerate() {
+            let c = symbol(previous, *point, wire.get(i + 1));
+            let cell = grid[index(*point)].get_or_insert((id, c));
+            if cell.0 != id {
+                cell.1 = 'X';
+            } else if cell.1 != c && cell.1 != 'X' {
+                cell.1 = '+';
+            }
+            previous = *point;
+        }
+    }
+    grid[index(origin)] = Some((0, 'o'));
+
+    let mut rendered = String::with_capacity((width + 1) * height);
+    for row in grid.chunks(width) {
+        rendered.extend(row.iter().map(|cell| cell.map_or('.', |cell| cell.1)));
+        rendered.push('\n');
+    }
+    rendered
+}
+
+// Writes the wires as a binary PPM image with +y pointing up. Each wire gets
+// its own color, cells shared by different wires are drawn as crossings and
+// the central port is highlighted. Large layouts are scaled down to fit.
+pub fn write_ppm(wires: &[Vec<Vec2d>], path: &Path) -> io::Result<()> {
+    let origin = Vec2d::ORIGIN;
+    let mut cells: HashMap<Vec2d, [u8; 3]> = HashMap::new();
+    let mut owners: HashMap<Vec2d, usize> = HashMap::new();
+
+    for (id, wire) in wires.iter().enumerate() {
+        let color = WIRE_COLORS[id % WIRE_COLORS.len()];
+        for point in wire {
+            let owner = owners.entry(*point).or_insert(id);
+            let cell = cells.entry(*point).or_insert(color);
+            if *owner != id {
+                *cell = CROSSING_COLOR;
+            }
+        }
+    }
+    cells.insert(origin, PORT_COLOR);
+
+    let points: Vec<Vec2d> = cells.keys().cloned().collect();
+    let (low, high) = bounding_box(&points);
+    let scale = max(high.x - low.x + 1, high.y - low.y + 1) / (MAX_IMAGE_SIDE + 1) + 1;
+    let width = (high.x - low.x) / scale + 1;
+    let height = (high.y - low.y) / scale + 1;
+
+    let mut pixels = vec![BACKGROUND; (width * height) as usize];
+    for (point, color) in &cells {
+        let index = ((high.y - point.y) / scale * width + (point.x - low.x) / scale) as usize;
+        // When several cells share a pixel, crossings and the port win.
+        if pixels[index] != CROSSING_COLOR && pixels[index] != PORT_COLOR {
+            pixels[index] = *color;
+        }
+    }
+    let port = ((high.y - origin.y) / scale * width + (origin.x - low.x) / scale) as usize;
+    pixels[port] = PORT_COLOR;
+
+    let mut out = BufWriter::new(File::create(path)?);
+    write!(out, "P6\n{} {}\n255\n", width, height)?;
+    for pixel in &pixels {
+        out.write_all(pixel)?;
+    }
+    out.flush()
+}
+
+fn rgb(color: [u8; 3]) -> String {
+    format!("rgb({},{},{})", color[0], color[1], color[2])
+}
+
+// Writes the wires as an SVG image. Each wire is a single polyline through its
+// corners and every point where two wires cross is marked with a circle. SVG
+// has +y pointing down, so y is flipped to keep +y pointing up as in the
+// puzzle text.
+pub fn write_svg(wires: &[Wire], path: &Path) -> io::Result<()> {
+    let corners: Vec<Vec<Vec2d>> = wires
+        .iter()
+        .map(|wire| {
+            wire.vertices()
+                .iter()
+                .scan(Vec2d::ORIGIN, |corner, vertex| {
+                    *corner += *vertex;
+                    Some(*corner)
+                })
+                .collect()
+        })
+        .collect();
+    let all_corners: Vec<Vec2d> = corners.iter().flatten().copied().collect();
+    let (low, high) = bounding_box(&all_corners);
+    let side = max(high.x - low.x, high.y - low.y) + 1;
+    let radius = max(side / 200, 1);
+
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+        low.x - radius,
+        -high.y - radius,
+        high.x - low.x + 2 * radius,
+        high.y - low.y + 2 * radius
+    )?;
+    for (id, wire) in corners.iter().enumerate() {
+        let points: Vec<String> = iter::once(&Vec2d::ORIGIN)
+            .chain(wire.iter())
+            .map(|corner| format!("{},{}", corner.x, -corner.y))
+            .collect();
+        writeln!(
+            out,
+            "  <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" \
+             vector-effect=\"non-scaling-stroke\"/>",
+            points.join(" "),
+            rgb(WIRE_COLORS[id % WIRE_COLORS.len()])
+        )?;
+    }
+
+    let points: Vec<Vec<Vec2d>> = wires.iter().map(|wire| wire.points().collect()).collect();
+    let mut marked = HashSet::new();
+    for (point, _, _) in all_pairwise_intersections(&points) {
+        if marked.insert(point) {
+            writeln!(
+                out,
+                "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>",
+                point.x,
+                -point.y,
+                radius,
+                rgb(CROSSING_COLOR)
+            )?;
+        }
+    }
+    writeln!(out, "</svg>")?;
+    out.flush()
+}
+
+// Writes the crossings of two wires, given by their points, as CSV with a
+// header row. The rows come closest to the central port first.
+pub fn write_crossings_csv(a: &[Vec2d], b: &[Vec2d], path: &Path) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "x,y,steps_a,steps_b,combined,manhattan")?;
+    for crossing in crossings(a, b) {
+        writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            crossing.point.x,
+            crossing.point.y,
+            crossing.steps_a,
+            crossing.steps_b,
+            crossing
+                .combined_steps()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?,
+            crossing.point.manhattan_distance()
+        )?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{get_points, parse};
+
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn render_grid_test() {
+        let wires = vec![
+            get_points(&parse("R8,U5,L5,D3").unwrap()),
+            get_points(&parse("U7,R6,D4,L4").unwrap()),
+        ];
+        let expected = "\
++-----+..
+|.....|..
+|..+--X-+
+|..|..|.|
+|.-X--+.|
+|..|....|
+|.......|
+o-------+
+";
+        assert_eq!(render_grid(&wires), expected);
+    }
+
+    #[test]
+    fn render_grid_negative_test() {
+        let wires = vec![
+            get_points(&parse("L3,D2,R1").unwrap()),
+            get_points(&parse("D1,L4").unwrap()),
+        ];
+        let expected = "\
+.+--o
+-X--+
+.+-..
+";
+        assert_eq!(render_grid(&wires), expected);
+        assert_eq!(render_grid(&[]), "o\n");
+    }
+
+    #[test]
+    fn write_svg_test() {
+        let wires: Vec<Wire> = vec![
+            "R8,U5,L5,D3".parse().unwrap(),
+            "U7,R6,D4,L4".parse().unwrap(),
+        ];
+        let path = env::temp_dir().join("day_3_write_svg_test.svg");
+
+        write_svg(&wires, &path).unwrap();
+        let image = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(image
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 -8 10 9\">\n"));
+        assert!(image.ends_with("</svg>\n"));
+        assert_eq!(image.matches("<polyline ").count(), 2);
+        assert!(image.contains("points=\"0,0 8,0 8,-5 3,-5 3,-2\""));
+        assert!(image.contains("points=\"0,0 0,-7 6,-7 6,-3 2,-3\""));
+        assert_eq!(image.matches("<circle ").count(), 2);
+        assert!(image.contains("<circle cx=\"3\" cy=\"-3\" "));
+        assert!(image.contains("<circle cx=\"6\" cy=\"-5\" "));
+    }
+
+    #[test]
+    fn write_crossings_csv_test() {
+        let a = get_points(&parse("R8,U5,L5,D3").unwrap());
+        let b = get_points(&parse("U7,R6,D4,L4").unwrap());
+        let path = env::temp_dir().join("day_3_write_crossings_csv_test.csv");
+
+        write_crossings_csv(&a, &b, &path).unwrap();
+        let csv = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let mut rows = csv.lines();
+        assert_eq!(rows.next(), Some("x,y,steps_a,steps_b,combined,manhattan"));
+        assert_eq!(
+            rows.collect::<Vec<&str>>(),
+            vec!["3,3,20,20,40,6", "6,5,15,15,30,11"]
+        );
+    }
+
+    #[test]
+    fn write_ppm_test() {
+        let wires = vec![
+            get_points(&parse("R8,U5,L5,D3").unwrap()),
+            get_points(&parse("U7,R6,D4,L4").unwrap()),
+        ];
+        let path = env::temp_dir().join("day_3_write_ppm_test.ppm");
+
+        write_ppm(&wires, &path).unwrap();
+        let image = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let header = b"P6\n9 8\n255\n";
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(image.len() - header.len(), 9 * 8 * 3);
+
+        let pixel = |x: usize, y: usize| {
+            let offset = header.len() + ((7 - y) * 9 + x) * 3;
+            [image[offset], image[offset + 1], image[offset + 2]]
+        };
+        assert_eq!(pixel(0, 0), PORT_COLOR);
+        assert_eq!(pixel(1, 0), WIRE_COLORS[0]);
+        assert_eq!(pixel(0, 1), WIRE_COLORS[1]);
+        assert_eq!(pixel(3, 3), CROSSING_COLOR);
+        assert_eq!(pixel(6, 5), CROSSING_COLOR);
+        assert_eq!(pixel(1, 1), BACKGROUND);
+    }
+}
@@ -0,0 +1,232 @@
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const EXAMPLE_2: &str = "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83\n";
+
+// An input file in the temporary directory, named after the process so that
+// concurrent test runs do not collide, and removed again when dropped, even
+// when an assertion fails.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new<C: AsRef<[u8]>>(contents: C) -> TempFile {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "day_3_cli_{}_{}.txt",
+            process::id(),
+            NEXT.fetch_add(1, Ordering::SeqCst)
+        );
+        let path = env::temp_dir().join(name);
+        fs::write(&path, contents).unwrap();
+        TempFile(path)
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+// Runs day_3 with the arguments followed by a file holding the input.
+fn run<C: AsRef<[u8]>>(args: &[&str], input: C) -> Output {
+    let input = TempFile::new(input);
+    Command::new(env!("CARGO_BIN_EXE_day_3"))
+        .args(args)
+        .arg(input.path())
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn input_file_argument_test() {
+    let output = run(&[], EXAMPLE_2);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Part 1: distance: 159\nPart 2: steps: 610 at (158, -12)\n\
+         Wire 1: length: 482, end: (145, 11), distance: 156\n\
+         Wire 2: length: 484, end: (238, -12), distance: 250\n"
+    );
+}
+
+#[test]
+fn byte_order_mark_test() {
+    let output = run(&[], [b"\xef\xbb\xbf", EXAMPLE_2.as_bytes()].concat());
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Part 1: distance: 159\nPart 2: steps: 610 at (158, -12)\n\
+         Wire 1: length: 482, end: (145, 11), distance: 156\n\
+         Wire 2: length: 484, end: (238, -12), distance: 250\n"
+    );
+}
+
+#[test]
+fn missing_input_file_test() {
+    // The file is gone again by the time day_3 looks for it.
+    let path = TempFile::new("").path().to_path_buf();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_day_3"))
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.starts_with("unable to read input"));
+    assert!(!stderr.contains("panicked"));
+}
+
+#[test]
+fn single_wire_test() {
+    let output = run(&[], "R8,U5,L5,D3\n");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "expected at least 2 wires, found 1\n"
+    );
+}
+
+#[test]
+fn no_intersections_test() {
+    let output = run(&[], "R8,U5\nU1,R7\n");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "no intersections found\n"
+    );
+}
+
+#[test]
+fn time_flag_test() {
+    let output = run(&["--time"], EXAMPLE_2);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("Part 1: distance: 159 ("));
+    assert!(lines[0].ends_with(")"));
+    assert!(lines[1].starts_with("Part 2: steps: 610 at (158, -12) ("));
+    assert!(lines[1].ends_with(")"));
+    assert_eq!(
+        lines[2..],
+        [
+            "Wire 1: length: 482, end: (145, 11), distance: 156",
+            "Wire 2: length: 484, end: (238, -12), distance: 250"
+        ]
+    );
+}
+
+#[test]
+fn json_flag_test() {
+    let output = run(&["--json"], "R8,U5,L5,D3\nU7,R6,D4,L4\n");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, "{\"part1\": 6, \"part2\": 30}\n");
+
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["part1"], 6);
+    assert_eq!(json["part2"], 30);
+}
+
+#[test]
+fn check_flag_test() {
+    // A single wire passes the check, though there is nothing to cross.
+    let output = run(&["--check"], "R8,U5,L5,D3\n");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Wire 1: segments: 4\n"
+    );
+}
+
+#[test]
+fn check_flag_malformed_test() {
+    let output = run(&["--check"], "R8,U5,L5,D3\n\nU7,R6,D,L4\n");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "line 3: invalid wire: missing magnitude\n"
+    );
+}
+
+#[test]
+fn file_and_stdin_test() {
+    let input = TempFile::new("R75,D30,R83,U83,L12,D49,R71,U7,L72\n");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_day_3"))
+        .arg("--json")
+        .arg(input.path())
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"U62,R66,U55,R34,D71,R55,D58,R83\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\"part1\": 159, \"part2\": 610}\n"
+    );
+}
+
+#[test]
+fn duplicate_wire_test() {
+    let input = "R8,U5,L5,D3\nR8,U5,L5,D3\n";
+
+    // The wires are still solved, every point they enter being a crossing.
+    let output = run(&["--json"], input);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\"part1\": 1, \"part2\": 2}\n"
+    );
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "warning: wire 2 duplicates wire 1\n"
+    );
+
+    let output = run(&["--strict"], input);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "wire 2 duplicates wire 1\n"
+    );
+}
+
+#[test]
+fn wires_through_port_test() {
+    let output = run(&["--json"], "R1,U1,L1,D1\nU1,R1,D1,L1,R2\n");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\"part1\": 1, \"part2\": 4}\n"
+    );
+}
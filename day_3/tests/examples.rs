@@ -0,0 +1,44 @@
+use common::input::read_lines;
+use day_3::{get_points, parse_wires, solve, solve_pairwise, Vec2d};
+
+use std::path::Path;
+
+fn solve_fixture(name: &str) -> (i32, u64) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+    let lines = read_lines(path.to_str()).unwrap();
+    let wires: Vec<Vec<Vec2d>> = parse_wires(&lines)
+        .unwrap()
+        .iter()
+        .map(|v| get_points(v))
+        .collect();
+
+    let answers = solve(&wires[0], &wires[1]).unwrap().unwrap();
+    assert_eq!(solve_pairwise(&wires).unwrap(), answers);
+    answers
+}
+
+#[test]
+fn example_1_test() {
+    assert_eq!(solve_fixture("example_1.txt"), (6, 30));
+}
+
+#[test]
+fn example_2_test() {
+    assert_eq!(solve_fixture("example_2.txt"), (159, 610));
+}
+
+#[test]
+fn example_3_test() {
+    assert_eq!(solve_fixture("example_3.txt"), (135, 410));
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip_example_2_test() {
+    assert_eq!(
+        solve_fixture("example_2.txt.gz"),
+        solve_fixture("example_2.txt")
+    );
+}
@@ -0,0 +1,27 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day_4::{count_valid, count_valid_combinatoric};
+
+fn count_valid_benchmark(c: &mut Criterion) {
+    c.bench_function("count_valid part one", |bencher| {
+        bencher.iter(|| count_valid(black_box(138_241..=674_034), false))
+    });
+    c.bench_function("count_valid part two", |bencher| {
+        bencher.iter(|| count_valid(black_box(138_241..=674_034), true))
+    });
+}
+
+fn count_valid_combinatoric_benchmark(c: &mut Criterion) {
+    c.bench_function("count_valid_combinatoric part one", |bencher| {
+        bencher.iter(|| count_valid_combinatoric(black_box(138_241..=674_034), false))
+    });
+    c.bench_function("count_valid_combinatoric part two", |bencher| {
+        bencher.iter(|| count_valid_combinatoric(black_box(138_241..=674_034), true))
+    });
+}
+
+criterion_group!(
+    benches,
+    count_valid_benchmark,
+    count_valid_combinatoric_benchmark
+);
+criterion_main!(benches);
@@ -0,0 +1,1057 @@
+use std::cmp::{max, min};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::ops::{Deref, RangeInclusive};
+use std::time::Instant;
+
+pub use common::report::DayAnswers;
+pub use common::{AocError, RangeParseError};
+
+pub const PASSWORD_LENGTH: usize = 6;
+
+// Parses a puzzle input like "138241-674034" into an inclusive range. Ranges
+// written like "138241..674035" leave out their upper bound instead; an empty
+// one comes back as the empty range 1..=0. Both bounds must be plain decimal
+// numbers, so "-5-10" is rejected rather than read as a negative bound.
+// Surrounding whitespace is ignored.
+pub fn parse_range(s: &str) -> Result<RangeInclusive<u32>, RangeParseError> {
+    let s = s.trim();
+    let exclusive = s.contains("..");
+    let bounds: Vec<&str> = if exclusive {
+        s.split("..").collect()
+    } else {
+        s.split('-').collect()
+    };
+    if bounds.len() != 2 {
+        return Err(RangeParseError::MalformedRange(s.to_string()));
+    }
+    let parse_bound = |b: &str| {
+        if b.is_empty() || !b.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(RangeParseError::InvalidBound(b.to_string()));
+        }
+        b.parse::<u32>()
+            .map_err(|_| RangeParseError::InvalidBound(b.to_string()))
+    };
+    let low = parse_bound(bounds[0])?;
+    let high = parse_bound(bounds[1])?;
+    if low > high {
+        return Err(RangeParseError::Reversed(low, high));
+    }
+
+    match (exclusive, high) {
+        (false, _) => Ok(low..=high),
+        (true, high) if high > low => Ok(low..=high - 1),
+        (true, _) => Ok(RangeInclusive::new(1, 0)),
+    }
+}
+
+// Parses the range on the first line of the puzzle input.
+pub fn parse_input<S: AsRef<str>>(lines: &[S]) -> Result<RangeInclusive<u32>, AocError> {
+    let input = lines.first().map_or("", |line| line.as_ref().trim());
+    Ok(parse_range(input)?)
+}
+
+// Parses one range per line, skipping blank lines. Errors name the line the
+// malformed range was found on.
+pub fn parse_ranges<S: AsRef<str>>(lines: &[S]) -> Result<Vec<RangeInclusive<u32>>, AocError> {
+    lines
+        .iter()
+        .map(|line| line.as_ref().trim())
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| parse_range(line).map_err(|e| AocError::from(e).at_line(i + 1)))
+        .collect()
+}
+
+// Enough digits for any u64.
+const MAX_DIGITS: usize = 20;
+
+// The decimal digits of a number, most significant digit first.
+#[derive(Clone, Copy, Debug)]
+pub struct Digits {
+    digits: [u8; MAX_DIGITS],
+    len: usize,
+}
+
+impl Deref for Digits {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.digits[..self.len]
+    }
+}
+
+impl Digits {
+    // Adds one in place, carrying into a new leading digit when all digits are
+    // nines.
+    fn increment(&mut self) {
+        for digit in self.digits[..self.len].iter_mut().rev() {
+            if *digit < 9 {
+                *digit += 1;
+                return;
+            }
+            *digit = 0;
+        }
+        self.digits[..=self.len].rotate_right(1);
+        self.digits[0] = 1;
+        self.len += 1;
+    }
+}
+
+pub fn digits(mut password: u64) -> Digits {
+    let mut digits = [0; MAX_DIGITS];
+    let mut len = 0;
+    loop {
+        digits[len] = (password % 10) as u8;
+        len += 1;
+        password /= 10;
+        if password == 0 {
+            break;
+        }
+    }
+    digits[..len].reverse();
+
+    Digits { digits, len }
+}
+
+// Walks the digits once from left to right, passing the digit and length of
+// every run of equal digits to on_run. Returns whether the digits never
+// decrease.
+fn scan_runs(digits: &[u8], mut on_run: impl FnMut(u8, usize)) -> bool {
+    let mut non_decreasing = true;
+    let mut run = 1;
+
+    for pair in digits.windows(2) {
+        non_decreasing = non_decreasing && pair[0] <= pair[1];
+        if pair[0] == pair[1] {
+            run += 1;
+        } else {
+            on_run(pair[0], run);
+            run = 1;
+        }
+    }
+    if let Some(last) = digits.last() {
+        on_run(*last, run);
+    }
+
+    non_decreasing
+}
+
+// Checks if some digit is repeated in a group of exactly target_len adjacent
+// digits, i.e. a group that is not part of a larger group.
+pub fn has_isolated_group(password: u64, target_len: usize) -> bool {
+    has_isolated_group_digits(&digits(password), target_len)
+}
+
+// Same as has_isolated_group for a password already split into its digits.
+pub fn has_isolated_group_digits(digits: &[u8], target_len: usize) -> bool {
+    let mut found = false;
+    scan_runs(digits, |_, run| found = found || run == target_len);
+    found
+}
+
+// Checks that the digits never decrease from left to right.
+pub fn is_non_decreasing(digits: &[u8]) -> bool {
+    digits.windows(2).all(|pair| pair[0] <= pair[1])
+}
+
+pub fn is_valid_part_two(password: u64, digit_count: usize) -> bool {
+    let digits = digits(password);
+    digits.len() == digit_count && is_valid_part_two_digits(&digits)
+}
+
+pub fn is_valid_part_one(password: u64, digit_count: usize) -> bool {
+    let digits = digits(password);
+    digits.len() == digit_count && is_valid_part_one_digits(&digits)
+}
+
+// Same as is_valid_part_two for a password already split into its digits, most
+// significant first. Any number of digits is accepted.
+pub fn is_valid_part_two_digits(digits: &[u8]) -> bool {
+    is_non_decreasing(digits) && has_isolated_group_digits(digits, 2)
+}
+
+// Same as is_valid_part_one for a password already split into its digits.
+pub fn is_valid_part_one_digits(digits: &[u8]) -> bool {
+    let mut repeated = false;
+    let non_decreasing = scan_runs(digits, |_, run| repeated = repeated || run >= 2);
+    non_decreasing && repeated
+}
+
+// Returns the smallest number no less than n whose digits never decrease, by
+// flattening the digits after the first decrease, e.g. 138241 -> 138888.
+// Past 3999999999, the largest such u32, there is none to jump to, so it
+// saturates at u32::MAX instead. That is above every six-digit range, and its
+// own digits decrease, so callers can tell it from a real candidate.
+pub fn next_non_decreasing(n: u32) -> u32 {
+    let mut digits = digits(u64::from(n));
+    if let Some(i) = (1..digits.len()).find(|&i| digits[i] < digits[i - 1]) {
+        let fill = digits[i - 1];
+        for digit in digits.digits[i..digits.len].iter_mut() {
+            *digit = fill;
+        }
+    }
+    let next = digits
+        .iter()
+        .fold(0u64, |n, digit| n * 10 + u64::from(*digit));
+    u32::try_from(next).unwrap_or(u32::MAX)
+}
+
+// Narrows a range down to the numbers with exactly six digits. The result is
+// empty when the range holds no such numbers.
+pub fn password_range(range: RangeInclusive<u32>) -> RangeInclusive<u32> {
+    let start = max(*range.start(), 10u32.pow(PASSWORD_LENGTH as u32 - 1));
+    let end = min(*range.end(), 10u32.pow(PASSWORD_LENGTH as u32) - 1);
+    start..=end
+}
+
+// Yields the six-digit numbers in a range whose digits never decrease, in
+// ascending order.
+pub struct NonDecreasing {
+    digits: [u32; PASSWORD_LENGTH],
+    end: u32,
+    done: bool,
+}
+
+impl NonDecreasing {
+    pub fn new(range: RangeInclusive<u32>) -> NonDecreasing {
+        let (start, end) = password_range(range).into_inner();
+        let start = next_non_decreasing(start);
+
+        let mut digits = [0; PASSWORD_LENGTH];
+        let mut n = start;
+        for digit in digits.iter_mut().rev() {
+            *digit = n % 10;
+            n /= 10;
+        }
+
+        NonDecreasing {
+            digits,
+            end,
+            done: start > end,
+        }
+    }
+
+    fn value(&self) -> u32 {
+        self.digits.iter().fold(0, |n, digit| n * 10 + digit)
+    }
+}
+
+impl Iterator for NonDecreasing {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.done {
+            return None;
+        }
+        let value = self.value();
+        if value > self.end {
+            self.done = true;
+            return None;
+        }
+
+        // Bump the rightmost digit that can grow and flatten the digits after
+        // it, e.g. 138899 -> 138999.
+        match self.digits.iter().rposition(|&digit| digit < 9) {
+            Some(i) => {
+                let fill = self.digits[i] + 1;
+                for digit in self.digits[i..].iter_mut() {
+                    *digit = fill;
+                }
+            }
+            None => self.done = true,
+        }
+        Some(value)
+    }
+}
+
+fn validator(part_two: bool) -> fn(u64, usize) -> bool {
+    if part_two {
+        is_valid_part_two
+    } else {
+        is_valid_part_one
+    }
+}
+
+// Yields the valid six-digit passwords in the range in ascending order.
+pub fn valid_iter(range: RangeInclusive<u32>, part_two: bool) -> impl Iterator<Item = u32> {
+    let is_valid = validator(part_two);
+    NonDecreasing::new(range)
+        .filter(move |password| is_valid(u64::from(*password), PASSWORD_LENGTH))
+}
+
+// Pairs each valid password with its 0-based offset from the start of the
+// range.
+pub fn valid_with_positions(range: RangeInclusive<u32>, part_two: bool) -> Vec<(usize, u32)> {
+    let start = *range.start();
+    valid_iter(range, part_two)
+        .map(|password| ((password - start) as usize, password))
+        .collect()
+}
+
+// Converts a parsed range to the u64 range count_valid takes.
+pub fn widen(range: RangeInclusive<u32>) -> RangeInclusive<u64> {
+    u64::from(*range.start())..=u64::from(*range.end())
+}
+
+// Counts the valid six-digit passwords in the range. The range is u64 like the
+// validators, but every six-digit number fits in a u32, so bounds past that
+// are clamped before iterating.
+pub fn count_valid(range: RangeInclusive<u64>, part_two: bool) -> usize {
+    let clamp = |n: u64| u32::try_from(n).unwrap_or(u32::MAX);
+    let (start, end) = range.into_inner();
+    valid_iter(clamp(start)..=clamp(end), part_two).count()
+}
+
+// Same as count_valid but counts chunks of the range in parallel.
+#[cfg(feature = "parallel")]
+pub fn count_valid_parallel(range: RangeInclusive<u32>, part_two: bool) -> usize {
+    use rayon::prelude::*;
+
+    const CHUNK_SIZE: u32 = 10_000;
+    let (start, end) = range.into_inner();
+    if start > end {
+        return 0;
+    }
+
+    (0..=(end - start) / CHUNK_SIZE)
+        .into_par_iter()
+        .map(|chunk| {
+            let low = start + chunk * CHUNK_SIZE;
+            let high = min(low.saturating_add(CHUNK_SIZE - 1), end);
+            count_valid(widen(low..=high), part_two)
+        })
+        .sum()
+}
+
+// Whether a finished run of equal digits satisfies the repetition rule.
+fn is_repetition(run: u8, part_two: bool) -> bool {
+    if part_two {
+        run == 2
+    } else {
+        run >= 2
+    }
+}
+
+// What needs to be remembered about the digits placed so far. Runs longer than
+// three digits are never told apart, so run is capped at three.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+struct RunState {
+    prev: u8,
+    run: u8,
+    found: bool,
+}
+
+impl RunState {
+    const EMPTY: RunState = RunState {
+        prev: 0,
+        run: 0,
+        found: false,
+    };
+
+    fn push(self, digit: u8, part_two: bool) -> RunState {
+        if digit == self.prev {
+            RunState {
+                run: min(self.run + 1, 3),
+                ..self
+            }
+        } else {
+            RunState {
+                prev: digit,
+                run: 1,
+                found: self.found || is_repetition(self.run, part_two),
+            }
+        }
+    }
+
+    fn is_valid(self, part_two: bool) -> bool {
+        self.found || is_repetition(self.run, part_two)
+    }
+
+    // The smallest digit that may come next, leading zeros are not allowed.
+    fn lowest_next(self) -> u8 {
+        max(self.prev, 1)
+    }
+}
+
+// Counts the ways to append a number of digits to a prefix without breaking the
+// rules, memoized on what the prefix leaves behind.
+struct Completions {
+    part_two: bool,
+    memo: HashMap<(usize, RunState), u64>,
+}
+
+impl Completions {
+    fn count(&mut self, remaining: usize, state: RunState) -> u64 {
+        if remaining == 0 {
+            return state.is_valid(self.part_two) as u64;
+        }
+        if let Some(count) = self.memo.get(&(remaining, state)) {
+            return *count;
+        }
+        let count = (state.lowest_next()..=9)
+            .map(|digit| self.count(remaining - 1, state.push(digit, self.part_two)))
+            .sum();
+        self.memo.insert((remaining, state), count);
+        count
+    }
+
+    // Counts the valid six-digit passwords that are no larger than the six-digit
+    // or shorter n.
+    fn up_to(&mut self, n: u32) -> u64 {
+        if n < 10u32.pow(PASSWORD_LENGTH as u32 - 1) {
+            return 0;
+        }
+
+        let mut count = 0;
+        let mut state = RunState::EMPTY;
+        for (i, &bound) in digits(u64::from(n)).iter().enumerate() {
+            let remaining = PASSWORD_LENGTH - i - 1;
+            for digit in state.lowest_next()..bound {
+                count += self.count(remaining, state.push(digit, self.part_two));
+            }
+            if bound < state.lowest_next() {
+                return count;
+            }
+            state = state.push(bound, self.part_two);
+        }
+        count + state.is_valid(self.part_two) as u64
+    }
+}
+
+// Same as count_valid but counts the passwords digit by digit instead of
+// visiting them one at a time, so the width of the range does not matter.
+pub fn count_valid_combinatoric(range: RangeInclusive<u32>, part_two: bool) -> u64 {
+    let (start, end) = password_range(range).into_inner();
+    if start > end {
+        return 0;
+    }
+    let mut completions = Completions {
+        part_two,
+        memo: HashMap::new(),
+    };
+    let below_start = start.checked_sub(1).map_or(0, |n| completions.up_to(n));
+    completions.up_to(end) - below_start
+}
+
+// Checks every number in a range one after the other, keeping the digits of
+// the last number checked. Each next number only has its digits bumped by one
+// instead of being taken apart again, and a scan that picks up where the last
+// one ended reuses them as well.
+#[derive(Clone, Debug, Default)]
+pub struct PasswordScanner {
+    last: Option<(u32, Digits)>,
+}
+
+impl PasswordScanner {
+    pub fn new() -> PasswordScanner {
+        PasswordScanner::default()
+    }
+
+    fn digits(&mut self, n: u32) -> &Digits {
+        let digits = match self.last {
+            Some((last, digits)) if last == n => digits,
+            Some((last, mut digits)) if last.checked_add(1) == Some(n) => {
+                digits.increment();
+                digits
+            }
+            _ => digits(u64::from(n)),
+        };
+        &self.last.insert((n, digits)).1
+    }
+
+    // Counts the valid six-digit passwords in the range.
+    pub fn count(&mut self, range: RangeInclusive<u32>, part_two: bool) -> usize {
+        let is_valid = if part_two {
+            is_valid_part_two_digits
+        } else {
+            is_valid_part_one_digits
+        };
+        password_range(range)
+            .filter(|n| is_valid(self.digits(*n)))
+            .count()
+    }
+}
+
+// Returns the number of valid passwords for both parts.
+pub fn solve(range: RangeInclusive<u32>) -> (usize, usize) {
+    let range = widen(range);
+    (count_valid(range.clone(), false), count_valid(range, true))
+}
+
+// Counts the part two passwords in the range by the digit that makes up their
+// group of exactly two. A password with several such groups is counted once,
+// under the smallest of their digits.
+pub fn valid_run_digit_histogram(range: RangeInclusive<u32>) -> [usize; 10] {
+    let mut histogram = [0; 10];
+    for password in valid_iter(range, true) {
+        let mut pair_digit = None;
+        scan_runs(&digits(u64::from(password)), |digit, run| {
+            if run == 2 && pair_digit.is_none() {
+                pair_digit = Some(digit);
+            }
+        });
+        if let Some(digit) = pair_digit {
+            histogram[digit as usize] += 1;
+        }
+    }
+    histogram
+}
+
+// Returns the length of the longest run of equal digits in any part one
+// password in the range, or 0 when there are none.
+pub fn max_run_length(range: RangeInclusive<u32>) -> usize {
+    let mut longest = 0;
+    for password in valid_iter(range, false) {
+        scan_runs(&digits(u64::from(password)), |_, run| {
+            longest = max(longest, run)
+        });
+    }
+    longest
+}
+
+// Same as solve, with the answers formatted for the runner.
+pub fn day_answers(range: RangeInclusive<u32>) -> DayAnswers {
+    let start = Instant::now();
+    let (part1, part2) = solve(range);
+    DayAnswers {
+        day: 4,
+        part1: part1.to_string(),
+        part2: part2.to_string(),
+        elapsed: start.elapsed(),
+    }
+}
+
+// Returns the valid six-digit passwords in the range in ascending order.
+pub fn valid_passwords(range: RangeInclusive<u32>, part_two: bool) -> Vec<u32> {
+    valid_iter(range, part_two).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn part_1_examples_test() {
+        assert!(is_valid_part_one(111111, 6));
+        assert!(!is_valid_part_one(223450, 6));
+        assert!(!is_valid_part_one(123789, 6));
+    }
+
+    #[test]
+    fn part_2_examples_test() {
+        assert!(is_valid_part_two(112233, 6));
+        assert!(!is_valid_part_two(123444, 6));
+        assert!(is_valid_part_two(111122, 6));
+        assert!(is_valid_part_two(112222, 6));
+    }
+
+    #[test]
+    fn digits_validators_test() {
+        assert!(is_valid_part_one_digits(&[1, 1, 1, 1, 1, 1]));
+        assert!(!is_valid_part_one_digits(&[2, 2, 3, 4, 5, 0]));
+        assert!(!is_valid_part_one_digits(&[1, 2, 3, 7, 8, 9]));
+        assert!(is_valid_part_one_digits(&[1, 1]));
+        assert!(!is_valid_part_one_digits(&[]));
+
+        assert!(is_valid_part_two_digits(&[1, 1, 2, 2, 3, 3]));
+        assert!(!is_valid_part_two_digits(&[1, 2, 3, 4, 4, 4]));
+        assert!(is_valid_part_two_digits(&[1, 1, 1, 1, 2, 2]));
+        assert!(!is_valid_part_two_digits(&[1, 1, 1]));
+
+        for password in 138_241..=140_000u64 {
+            let digits = digits(password);
+            assert_eq!(
+                is_valid_part_one_digits(&digits),
+                is_valid_part_one(password, 6)
+            );
+            assert_eq!(
+                is_valid_part_two_digits(&digits),
+                is_valid_part_two(password, 6)
+            );
+        }
+    }
+
+    #[test]
+    fn has_isolated_group_test() {
+        assert!(has_isolated_group(123444, 3));
+        assert!(!has_isolated_group(123444, 2));
+        assert!(has_isolated_group(111122, 2));
+        assert!(has_isolated_group(111122, 4));
+        assert!(!has_isolated_group(111122, 3));
+        assert!(has_isolated_group(111111, 6));
+        assert!(has_isolated_group(123456, 1));
+        assert!(!has_isolated_group(112233, 1));
+
+        assert!(has_isolated_group_digits(&[1, 2, 3, 4, 4, 4], 3));
+        assert!(!has_isolated_group_digits(&[1, 2, 3, 4, 4, 4], 2));
+        assert!(!has_isolated_group_digits(&[], 1));
+    }
+
+    #[test]
+    fn is_non_decreasing_test() {
+        assert!(is_non_decreasing(&[1, 1, 2, 3, 9, 9]));
+        assert!(!is_non_decreasing(&[2, 2, 3, 4, 5, 0]));
+        assert!(is_non_decreasing(&[7]));
+        assert!(is_non_decreasing(&[]));
+    }
+
+    #[test]
+    fn digit_count_test() {
+        assert!(!is_valid_part_one(11111, 6));
+        assert!(is_valid_part_one(11111, 5));
+        assert!(!is_valid_part_one(1111111, 6));
+        assert!(is_valid_part_one(1122, 4));
+        assert!(is_valid_part_one(12345677, 8));
+        assert!(!is_valid_part_two(11233, 6));
+        assert!(is_valid_part_two(11233, 5));
+        assert!(is_valid_part_two(12345677, 8));
+    }
+
+    #[test]
+    fn wide_password_test() {
+        assert!(is_valid_part_two(11_223_344_556_677, 14));
+        assert!(!is_valid_part_two(11_112_223_334_445, 14));
+        assert!(is_valid_part_one(11_112_223_334_445, 14));
+        assert!(!is_valid_part_one(11_223_344_556_676, 14));
+        assert!(is_valid_part_one(11_111_111_111_111_111_111, 20));
+        assert!(has_isolated_group(11_223_344_556_677, 2));
+    }
+
+    #[test]
+    fn digits_test() {
+        assert_eq!(*digits(123789), [1, 2, 3, 7, 8, 9]);
+        assert_eq!(*digits(0), [0]);
+        assert_eq!(*digits(9), [9]);
+        assert_eq!(*digits(10), [1, 0]);
+        assert_eq!(*digits(u64::from(u32::MAX)), [4, 2, 9, 4, 9, 6, 7, 2, 9, 5]);
+        assert_eq!(
+            *digits(u64::MAX),
+            [1, 8, 4, 4, 6, 7, 4, 4, 0, 7, 3, 7, 0, 9, 5, 5, 1, 6, 1, 5]
+        );
+    }
+
+    #[test]
+    fn digits_len_test() {
+        assert_eq!(digits(0).len(), 1);
+        assert_eq!(digits(9).len(), 1);
+        assert_eq!(digits(10).len(), 2);
+        assert_eq!(digits(138_241).len(), 6);
+        assert_eq!(digits(u64::from(u32::MAX)).len(), 10);
+        assert_eq!(digits(u64::MAX).len(), 20);
+    }
+
+    #[test]
+    fn solve_test() {
+        assert_eq!(solve(138_241..=674_034), (1890, 1277));
+        assert_eq!(solve(111_111..=111_130), (17, 1));
+        assert_eq!(solve(1_000_000..=2_000_000), (0, 0));
+    }
+
+    #[test]
+    fn count_valid_test() {
+        assert_eq!(count_valid(138_241..=674_034, false), 1890);
+        assert_eq!(count_valid(138_241..=674_034, true), 1277);
+        assert_eq!(count_valid(11_110..=111_112, false), 2);
+
+        // Bounds past u32::MAX hold no more six-digit passwords.
+        assert_eq!(
+            count_valid(138_241..=u64::MAX, false),
+            count_valid(138_241..=999_999, false)
+        );
+        assert_eq!(count_valid(5_000_000_000..=u64::MAX, true), 0);
+        assert_eq!(count_valid(widen(138_241..=674_034), true), 1277);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn count_valid_parallel_test() {
+        let test_cases = [
+            138_241..=674_034,
+            123_456..=234_567,
+            111_111..=111_111,
+            999_000..=u32::MAX,
+            99_999..=1_000_001,
+        ];
+        for range in test_cases.iter() {
+            for part_two in [false, true].iter() {
+                assert_eq!(
+                    count_valid_parallel(range.clone(), *part_two),
+                    count_valid(widen(range.clone()), *part_two)
+                );
+            }
+        }
+        assert_eq!(count_valid_parallel(138_241..=674_034, false), 1890);
+        assert_eq!(count_valid_parallel(138_241..=674_034, true), 1277);
+    }
+
+    #[test]
+    fn count_valid_combinatoric_test() {
+        assert_eq!(count_valid_combinatoric(138_241..=674_034, false), 1890);
+        assert_eq!(count_valid_combinatoric(138_241..=674_034, true), 1277);
+
+        let test_cases = [
+            123_456..=234_567,
+            111_111..=111_111,
+            111_111..=111_130,
+            138_890..=138_898,
+            199_999..=200_000,
+            0..=99_999,
+            0..=u32::MAX,
+            999_000..=u32::MAX,
+            RangeInclusive::new(674_034, 138_241),
+        ];
+        for range in test_cases.iter() {
+            for part_two in [false, true].iter() {
+                assert_eq!(
+                    count_valid_combinatoric(range.clone(), *part_two),
+                    count_valid(widen(range.clone()), *part_two) as u64
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn password_range_test() {
+        assert_eq!(password_range(99_999..=1_000_001), 100_000..=999_999);
+        assert_eq!(password_range(138_241..=674_034), 138_241..=674_034);
+        assert!(password_range(5_000..=99_999).is_empty());
+        assert!(password_range(1_000_000..=2_000_000).is_empty());
+    }
+
+    #[test]
+    fn only_six_digits_test() {
+        let range = 99_999..=1_000_001;
+        for part_two in [false, true].iter() {
+            let valid = valid_passwords(range.clone(), *part_two);
+            let six_digits = valid_passwords(100_000..=999_999, *part_two);
+
+            assert_eq!(valid, six_digits);
+            assert_eq!(
+                valid.first(),
+                Some(&if *part_two { 111_122 } else { 111_111 })
+            );
+            assert_eq!(
+                valid.last(),
+                Some(&if *part_two { 889_999 } else { 999_999 })
+            );
+            assert_eq!(count_valid(widen(range.clone()), *part_two), valid.len());
+            assert_eq!(
+                count_valid_combinatoric(range.clone(), *part_two),
+                valid.len() as u64
+            );
+        }
+
+        // Five- and seven-digit numbers that would otherwise pass the rules.
+        assert!(is_valid_part_one(99_999, 5));
+        assert!(is_valid_part_one(1_111_111, 7));
+        assert!(!valid_passwords(99_999..=1_111_111, false).contains(&99_999));
+        assert!(!valid_passwords(99_999..=1_111_111, false).contains(&1_111_111));
+        assert_eq!(count_valid(5_000..=99_999, false), 0);
+        assert_eq!(count_valid_combinatoric(5_000..=99_999, false), 0);
+    }
+
+    #[test]
+    fn valid_iter_test() {
+        assert_eq!(
+            valid_iter(138_241..=674_034, false)
+                .take(3)
+                .collect::<Vec<u32>>(),
+            vec![138_888, 138_889, 138_899]
+        );
+        assert_eq!(
+            valid_iter(138_241..=674_034, true)
+                .take(3)
+                .collect::<Vec<u32>>(),
+            vec![138_899, 144_455, 144_466]
+        );
+        assert_eq!(valid_iter(138_890..=138_898, false).next(), None);
+    }
+
+    #[test]
+    fn valid_with_positions_test() {
+        assert_eq!(
+            valid_with_positions(111_120..=111_130, false),
+            vec![
+                (2, 111_122),
+                (3, 111_123),
+                (4, 111_124),
+                (5, 111_125),
+                (6, 111_126),
+                (7, 111_127),
+                (8, 111_128),
+                (9, 111_129),
+            ]
+        );
+        assert_eq!(
+            valid_with_positions(111_120..=111_130, true),
+            vec![(2, 111_122)]
+        );
+        assert_eq!(
+            valid_with_positions(99_990..=111_112, false),
+            vec![(11_121, 111_111), (11_122, 111_112)]
+        );
+    }
+
+    #[test]
+    fn valid_passwords_test() {
+        assert_eq!(
+            valid_passwords(111_111..=111_130, false),
+            vec![
+                111_111, 111_112, 111_113, 111_114, 111_115, 111_116, 111_117, 111_118, 111_119,
+                111_122, 111_123, 111_124, 111_125, 111_126, 111_127, 111_128, 111_129,
+            ]
+        );
+        assert_eq!(valid_passwords(111_111..=111_130, true), vec![111_122]);
+        assert_eq!(valid_passwords(138_241..=674_034, false).len(), 1890);
+        assert_eq!(valid_passwords(138_241..=674_034, true).len(), 1277);
+    }
+
+    #[test]
+    fn valid_run_digit_histogram_test() {
+        assert_eq!(
+            valid_run_digit_histogram(111_111..=111_130),
+            [0, 0, 1, 0, 0, 0, 0, 0, 0, 0]
+        );
+        // 123445 to 123449 pair up the fours, 123455 to 123499 the last digit.
+        assert_eq!(
+            valid_run_digit_histogram(123_444..=123_499),
+            [0, 0, 0, 0, 5, 1, 1, 1, 1, 1]
+        );
+
+        let histogram = valid_run_digit_histogram(138_241..=674_034);
+        assert_eq!(histogram.iter().sum::<usize>(), 1277);
+        assert_eq!(histogram[0], 0);
+    }
+
+    #[test]
+    fn digits_increment_test() {
+        let cases: [(u64, u64); 4] = [(0, 1), (138_241, 138_242), (138_299, 138_300), (999, 1000)];
+        for (n, expected) in cases.iter() {
+            let mut incremented = digits(*n);
+            incremented.increment();
+            assert_eq!(*incremented, *digits(*expected));
+        }
+    }
+
+    #[test]
+    fn password_scanner_test() {
+        let mut scanner = PasswordScanner::new();
+        assert_eq!(scanner.count(138_241..=674_034, false), 1890);
+        assert_eq!(scanner.count(138_241..=674_034, true), 1277);
+
+        // Overlapping and adjoining ranges agree with the stateless count.
+        for range in [
+            111_111..=111_130,
+            111_125..=123_456,
+            123_457..=999_999,
+            5..=5,
+        ]
+        .iter()
+        {
+            for part_two in [false, true].iter() {
+                assert_eq!(
+                    scanner.count(range.clone(), *part_two),
+                    count_valid(widen(range.clone()), *part_two)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn max_run_length_test() {
+        assert_eq!(max_run_length(111_111..=111_130), 6);
+        assert_eq!(max_run_length(111_112..=111_130), 5);
+        assert_eq!(max_run_length(123_444..=123_499), 3);
+        assert_eq!(max_run_length(123_445..=123_499), 2);
+        assert_eq!(max_run_length(123_456..=123_456), 0);
+        assert_eq!(max_run_length(138_241..=674_034), 6);
+    }
+
+    #[test]
+    fn day_answers_test() {
+        let answers = day_answers(138_241..=674_034);
+
+        assert_eq!(answers.day, 4);
+        assert_eq!(answers.part1, "1890");
+        assert_eq!(answers.part2, "1277");
+        assert!(answers.elapsed > Duration::from_secs(0));
+    }
+
+    #[test]
+    fn non_decreasing_test() {
+        assert_eq!(
+            NonDecreasing::new(138_241..=138_900).collect::<Vec<u32>>(),
+            vec![138_888, 138_889, 138_899]
+        );
+        assert_eq!(
+            NonDecreasing::new(99_990..=111_112).collect::<Vec<u32>>(),
+            vec![111_111, 111_112]
+        );
+        assert_eq!(
+            NonDecreasing::new(999_990..=2_000_000).collect::<Vec<u32>>(),
+            vec![999_999]
+        );
+        assert_eq!(NonDecreasing::new(1_000_000..=2_000_000).next(), None);
+        assert_eq!(NonDecreasing::new(138_890..=138_898).next(), None);
+    }
+
+    #[test]
+    fn next_non_decreasing_test() {
+        assert_eq!(next_non_decreasing(138_241), 138_888);
+        assert_eq!(next_non_decreasing(138_888), 138_888);
+        assert_eq!(next_non_decreasing(199_990), 199_999);
+        assert_eq!(next_non_decreasing(200_000), 222_222);
+        assert_eq!(next_non_decreasing(0), 0);
+        assert_eq!(next_non_decreasing(10), 11);
+        assert_eq!(next_non_decreasing(3_999_999_999), 3_999_999_999);
+        assert_eq!(next_non_decreasing(4_000_000_000), u32::MAX);
+        assert_eq!(next_non_decreasing(u32::MAX), u32::MAX);
+
+        let non_decreasing = |n: u32| is_non_decreasing(&digits(u64::from(n)));
+        // Walk down from a non-decreasing number, remembering the last one seen.
+        let mut next = 222_222;
+        for n in (138_241..=222_222).rev() {
+            if non_decreasing(n) {
+                next = n;
+            }
+            assert_eq!(next_non_decreasing(n), next);
+        }
+    }
+
+    #[test]
+    fn non_decreasing_matches_brute_force_test() {
+        let range = 138_241..=674_034;
+        let brute_force: Vec<u32> = range
+            .clone()
+            .filter(|password| is_valid_part_one(u64::from(*password), PASSWORD_LENGTH))
+            .collect();
+        let constructive: Vec<u32> = NonDecreasing::new(range.clone())
+            .filter(|password| is_valid_part_one(u64::from(*password), PASSWORD_LENGTH))
+            .collect();
+        assert_eq!(constructive, brute_force);
+        assert_eq!(constructive.len(), 1890);
+
+        let brute_force = range
+            .clone()
+            .filter(|password| is_valid_part_two(u64::from(*password), PASSWORD_LENGTH))
+            .count();
+        assert_eq!(count_valid(widen(range), true), brute_force);
+        assert_eq!(brute_force, 1277);
+    }
+
+    #[test]
+    fn parse_range_test() {
+        assert_eq!(parse_range("138241-674034"), Ok(138_241..=674_034));
+        assert_eq!(parse_range("5-5"), Ok(5..=5));
+        assert_eq!(parse_range(" 138241-674034\r\n"), Ok(138_241..=674_034));
+        assert_eq!(parse_range("138241..674035"), Ok(138_241..=674_034));
+        assert_eq!(parse_range("5..6"), Ok(5..=5));
+        assert!(parse_range("5..5").unwrap().is_empty());
+        assert!(parse_range("0..0").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_range_exclusive_count_test() {
+        let inclusive = parse_range("138241-674034").unwrap();
+        let exclusive = parse_range("138241..674035").unwrap();
+        assert_eq!(count_valid(widen(inclusive), false), 1890);
+        assert_eq!(count_valid(widen(exclusive), false), 1890);
+
+        // 111122 is the only part two password from 111111 to 111130.
+        assert_eq!(
+            count_valid(widen(parse_range("111111-111122").unwrap()), true),
+            1
+        );
+        assert_eq!(
+            count_valid(widen(parse_range("111111..111122").unwrap()), true),
+            0
+        );
+        assert_eq!(
+            count_valid(widen(parse_range("111111..111123").unwrap()), true),
+            1
+        );
+    }
+
+    #[test]
+    fn parse_input_test() {
+        assert_eq!(
+            parse_input(&["138241-674034\r", ""]).unwrap(),
+            138_241..=674_034
+        );
+        assert!(matches!(
+            parse_input(&["674034-138241"]),
+            Err(AocError::Range(RangeParseError::Reversed(674_034, 138_241)))
+        ));
+        assert!(matches!(
+            parse_input::<&str>(&[]),
+            Err(AocError::Range(RangeParseError::MalformedRange(_)))
+        ));
+    }
+
+    #[test]
+    fn parse_ranges_test() {
+        assert_eq!(
+            parse_ranges(&["138241-674034", "", " 5-5\r", "111111-111130"]).unwrap(),
+            vec![138_241..=674_034, 5..=5, 111_111..=111_130]
+        );
+        assert_eq!(parse_ranges::<&str>(&[]).unwrap(), vec![]);
+
+        let e = parse_ranges(&["138241-674034", "", "5-x"]).unwrap_err();
+        assert!(matches!(
+            e,
+            AocError::Line { line: 3, ref source }
+                if matches!(**source, AocError::Range(RangeParseError::InvalidBound(_)))
+        ));
+        assert_eq!(e.to_string(), "line 3: invalid range: invalid bound: \"x\"");
+    }
+
+    #[test]
+    fn parse_range_error_test() {
+        let test_cases = [
+            (
+                "138241",
+                RangeParseError::MalformedRange("138241".to_string()),
+            ),
+            (
+                "1-2-3",
+                RangeParseError::MalformedRange("1-2-3".to_string()),
+            ),
+            ("", RangeParseError::MalformedRange("".to_string())),
+            (
+                "abc-674034",
+                RangeParseError::InvalidBound("abc".to_string()),
+            ),
+            ("138241-", RangeParseError::InvalidBound("".to_string())),
+            ("674034-138241", RangeParseError::Reversed(674_034, 138_241)),
+            (
+                "138-241-674",
+                RangeParseError::MalformedRange("138-241-674".to_string()),
+            ),
+            (
+                "-5-10",
+                RangeParseError::MalformedRange("-5-10".to_string()),
+            ),
+            ("abc-def", RangeParseError::InvalidBound("abc".to_string())),
+            ("+5-10", RangeParseError::InvalidBound("+5".to_string())),
+            ("5 - 10", RangeParseError::InvalidBound("5 ".to_string())),
+            (
+                "1-99999999999",
+                RangeParseError::InvalidBound("99999999999".to_string()),
+            ),
+            (
+                "1..2..3",
+                RangeParseError::MalformedRange("1..2..3".to_string()),
+            ),
+            ("1..x", RangeParseError::InvalidBound("x".to_string())),
+            (
+                "674034..138241",
+                RangeParseError::Reversed(674_034, 138_241),
+            ),
+        ];
+        for case in test_cases.iter() {
+            assert_eq!(parse_range(case.0), Err(case.1.clone()));
+        }
+    }
+}
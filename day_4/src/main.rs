@@ -46,79 +46,53 @@
 //
 // Both parts of this puzzle are complete! They provide two gold stars: **
 
-fn is_valid_part_two(mut password: i32) -> bool {
-    let mut valid = false;
-    let mut repeat_count = 1;
-    let mut previous = password % 10;
-    password /= 10;
+use common::args::Args;
+use common::input::read_lines_from_all;
+use common::report::Report;
+use day_4::{count_valid, parse_range, parse_ranges, widen, AocError};
 
-    while password > 0 {
-        let current = password % 10;
-        if current > previous {
-            return false;
-        }
+use std::env;
+use std::ops::RangeInclusive;
+use std::process;
+use std::time::Instant;
+
+// The environment variable a single range can be given in, like
+// AOC_RANGE=138241-674034.
+const RANGE_VAR: &str = "AOC_RANGE";
 
-        if current == previous {
-            repeat_count += 1;
-        } else {
-            valid = valid || repeat_count == 2;
-            repeat_count = 1;
+// Input files given as arguments come first, then the range in AOC_RANGE, and
+// stdin is only read when neither is given.
+fn read_ranges(args: &Args) -> Result<Vec<RangeInclusive<u32>>, AocError> {
+    if args.paths.is_empty() {
+        if let Ok(range) = env::var(RANGE_VAR) {
+            return Ok(vec![parse_range(&range)?]);
         }
-        previous = current;
-        password /= 10;
     }
-
-    valid || repeat_count == 2
+    parse_ranges(&read_lines_from_all(&args.paths)?)
 }
 
+// Prints a block of answers for every range in the input, one range per line.
+fn run(args: &Args) -> Result<(), AocError> {
+    for range in read_ranges(args)? {
+        println!("Range: {}-{}", range.start(), range.end());
+        let range = widen(range);
 
-fn is_valid_part_one(mut password: i32) -> bool {
-    let mut valid = false;
-    let mut previous = password % 10;
-    password /= 10;
+        let start = Instant::now();
+        let count = count_valid(range.clone(), false);
+        let part1 = format!("count: {}{}", count, args.timing(start.elapsed()));
 
-    while password > 0 {
-        let current = password % 10;
-        // Don't allow digits to decrease
-        if current > previous {
-            return false;
-        }
-        // Set valid flag if we have repeating digits
-        valid = valid || current == previous;
-        previous = current;
-        password /= 10;
-    }
+        let start = Instant::now();
+        let count = count_valid(range, true);
+        let part2 = format!("count: {}{}", count, args.timing(start.elapsed()));
 
-    valid
+        Report { part1, part2 }.print();
+    }
+    Ok(())
 }
 
 fn main() {
-    let valid_passwords = (138_241..=674_034)
-        .filter(|password| is_valid_part_one(*password))
-        .count();
-    println!("Part one. Count: {}", valid_passwords);
-    let valid_passwords = (138_241..=674_034)
-        .filter(|password| is_valid_part_two(*password))
-        .count();
-    println!("Part two: Count: {}", valid_passwords);
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn part_1_examples_test() {
-        assert_eq!(is_valid_part_one(111111), true);
-        assert_eq!(is_valid_part_one(223450), false);
-        assert_eq!(is_valid_part_one(123789), false);
-    }
-
-    #[test]
-    fn part_2_examples_test() {
-        assert_eq!(is_valid_part_two(112233), true);
-        assert_eq!(is_valid_part_two(123444), false);
-        assert_eq!(is_valid_part_two(111122), true);
-        assert_eq!(is_valid_part_two(112222), true);
+    if let Err(e) = run(&Args::from_env()) {
+        eprintln!("{}", e);
+        process::exit(1);
     }
 }
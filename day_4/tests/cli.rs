@@ -0,0 +1,127 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// An input file in the temporary directory, named after the process so that
+// concurrent test runs do not collide, and removed again when dropped, even
+// when an assertion fails.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(contents: &str) -> TempFile {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "day_4_cli_{}_{}.txt",
+            process::id(),
+            NEXT.fetch_add(1, Ordering::SeqCst)
+        );
+        let path = env::temp_dir().join(name);
+        fs::write(&path, contents).unwrap();
+        TempFile(path)
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+// Runs day_4 with the arguments followed by a file holding the input.
+fn run(args: &[&str], input: &str) -> Output {
+    let input = TempFile::new(input);
+    Command::new(env!("CARGO_BIN_EXE_day_4"))
+        .args(args)
+        .arg(input.path())
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn multiple_ranges_test() {
+    let output = run(&[], "138241-674034\n\n111111-111130\r\n5-5\n");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Range: 138241-674034\nPart 1: count: 1890\nPart 2: count: 1277\n\
+         Range: 111111-111130\nPart 1: count: 17\nPart 2: count: 1\n\
+         Range: 5-5\nPart 1: count: 0\nPart 2: count: 0\n"
+    );
+}
+
+#[test]
+fn malformed_range_test() {
+    let output = run(&[], "138241-674034\n\n111111\n");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "line 3: invalid range: expected two bounds separated by '-' or '..', got \"111111\"\n"
+    );
+}
+
+#[test]
+fn missing_input_file_test() {
+    // The file is gone again by the time day_4 looks for it.
+    let path = TempFile::new("").path().to_path_buf();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_day_4"))
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.starts_with("unable to read input"));
+    assert!(!stderr.contains("panicked"));
+}
+
+#[test]
+fn range_variable_test() {
+    let output = Command::new(env!("CARGO_BIN_EXE_day_4"))
+        .env("AOC_RANGE", "138241-674034")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Range: 138241-674034\nPart 1: count: 1890\nPart 2: count: 1277\n"
+    );
+
+    // The variable is validated like any other range.
+    let output = Command::new(env!("CARGO_BIN_EXE_day_4"))
+        .env("AOC_RANGE", "138241")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "invalid range: expected two bounds separated by '-' or '..', got \"138241\"\n"
+    );
+}
+
+#[test]
+fn input_file_over_range_variable_test() {
+    let input = TempFile::new("111111-111130\n");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_day_4"))
+        .arg(input.path())
+        .env("AOC_RANGE", "138241-674034")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Range: 111111-111130\nPart 1: count: 17\nPart 2: count: 1\n"
+    );
+}
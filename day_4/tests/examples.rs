@@ -0,0 +1,14 @@
+use common::input::read_lines;
+use day_4::{parse_input, solve};
+
+use std::path::Path;
+
+#[test]
+fn small_range_test() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/small_range.txt");
+    let lines = read_lines(path.to_str()).unwrap();
+    let range = parse_input(&lines).unwrap();
+
+    assert_eq!(range, 111_111..=111_130);
+    assert_eq!(solve(range), (17, 1));
+}